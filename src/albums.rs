@@ -0,0 +1,138 @@
+use rspotify::model::TimeRange;
+
+use crate::client::TopTracks;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TopAlbum {
+    pub index: usize,
+    pub album_name: String,
+    pub artists: Vec<String>,
+    pub release_year: Option<i32>,
+    pub track_count: usize,
+    pub score: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TopAlbums {
+    pub time_range: TimeRange,
+    pub albums: Vec<TopAlbum>,
+}
+
+/// Parses the year out of a Spotify release date, which may be `YYYY`,
+/// `YYYY-MM` or `YYYY-MM-DD` depending on its precision.
+pub fn release_year(release_date: &str) -> Option<i32> {
+    release_date.get(..4)?.parse().ok()
+}
+
+/// Ranks albums by aggregating the top tracks that belong to them.
+///
+/// Each track contributes a weight based on its rank, so the #1 track of a
+/// list of 50 is worth 50 points and the last one is worth 1. Ties are broken
+/// by the number of tracks and then by the best rank of any of the tracks.
+pub fn get_top_albums(top_tracks: &TopTracks) -> TopAlbums {
+    let total = top_tracks.tracks.len();
+    let mut albums: Vec<(TopAlbum, usize)> = Vec::new();
+
+    for track in &top_tracks.tracks {
+        let weight = total + 1 - track.index;
+
+        let existing = albums.iter_mut().find(|(album, _)| {
+            album.album_name == track.album_name && album.artists == track.album_artists
+        });
+
+        match existing {
+            Some((album, _)) => {
+                album.score += weight;
+                album.track_count += 1;
+            }
+            None => {
+                let album = TopAlbum {
+                    index: 0,
+                    album_name: track.album_name.clone(),
+                    artists: track.album_artists.clone(),
+                    release_year: track.release_date.as_deref().and_then(release_year),
+                    track_count: 1,
+                    score: weight,
+                };
+                albums.push((album, track.index));
+            }
+        }
+    }
+
+    albums.sort_by(|(a, a_best), (b, b_best)| {
+        b.score
+            .cmp(&a.score)
+            .then(b.track_count.cmp(&a.track_count))
+            .then(a_best.cmp(b_best))
+    });
+
+    let albums = albums
+        .into_iter()
+        .enumerate()
+        .map(|(i, (album, _))| TopAlbum {
+            index: i + 1,
+            ..album
+        })
+        .collect();
+
+    TopAlbums {
+        time_range: top_tracks.time_range,
+        albums,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::TopTrack;
+
+    fn track(index: usize, name: &str, album: &str, release_date: &str) -> TopTrack {
+        TopTrack {
+            index,
+            id: None,
+            track_name: name.to_string(),
+            duration: "4:00".to_string(),
+            duration_ms: 240_000,
+            artists: vec!["Massive Attack".to_string()],
+            album_id: None,
+            album_name: album.to_string(),
+            album_artists: vec!["Massive Attack".to_string()],
+            release_date: Some(release_date.to_string()),
+            popularity: 60,
+        }
+    }
+
+    #[test]
+    fn adds_up_the_tracks_of_each_album() {
+        let top_tracks = TopTracks {
+            time_range: TimeRange::MediumTerm,
+            tracks: vec![
+                track(1, "Teardrop", "Mezzanine", "1998-04-20"),
+                track(2, "Boxing Day", "Boxing Day", "2026-05"),
+                track(3, "Angel", "Mezzanine", "1998-04-20"),
+            ],
+        };
+
+        let albums = get_top_albums(&top_tracks).albums;
+
+        let summary: Vec<(usize, &str, usize, usize, Option<i32>)> = albums
+            .iter()
+            .map(|a| {
+                (
+                    a.index,
+                    a.album_name.as_str(),
+                    a.score,
+                    a.track_count,
+                    a.release_year,
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                (1, "Mezzanine", 3 + 1, 2, Some(1998)),
+                (2, "Boxing Day", 2, 1, Some(2026)),
+            ]
+        );
+    }
+}
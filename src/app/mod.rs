@@ -0,0 +1,16 @@
+//! The TUI, written in the Elm style: a [`Model`] holding all of the state,
+//! [`Message`]s describing everything that can happen to it, a pure
+//! [`update`] that applies them and returns [`Command`]s for side effects,
+//! and [`view`] components that draw the model.
+
+mod model;
+mod pane;
+mod update;
+pub mod view;
+
+pub use model::{
+    Detail, DetailRequest, DevicePicker, InputMode, Model, RunningState, Tab, Toast, ToastKind,
+};
+pub use pane::{Pane, PaneState, SortKey, TableCell};
+pub use update::{update, Command, Message};
+pub use view::draw;
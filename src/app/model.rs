@@ -0,0 +1,900 @@
+use std::collections::{HashMap, HashSet};
+
+use chrono::{DateTime, Local};
+use ratatui::layout::{Position, Rect};
+use rspotify::model::{AlbumId, ArtistId, TimeRange, TrackId};
+use tui_input::Input;
+
+use super::pane::{Pane, PaneState, SortKey, TableCell};
+use crate::albums::TopAlbums;
+use crate::client::{
+    show_time_range, ArtistDetails, NowPlaying, TopArtist, TopArtists, TopTrack, TopTracks,
+    TrackDetails, TIME_RANGES,
+};
+use crate::discover::{auto_seeds, DiscoverOptions, Recommendation, Seed, MAX_SEEDS};
+use crate::eras::ReleaseEras;
+use crate::graph::{ArtistGraph, GraphArtist, GraphOptions, GraphSummary};
+use crate::keymap::Keymap;
+use crate::metrics::ListeningMetrics;
+use crate::playback::PlaybackDevice;
+use crate::playlist::{PlaylistOptions, PlaylistPlan};
+use crate::releases::{Release, ReleaseOptions};
+use crate::report::{get_report, Report};
+use crate::results::RangeResults;
+use crate::search::{fuzzy_match, matches_any};
+use crate::theme::Theme;
+
+/// How many ticks an informational toast stays visible; errors stay twice
+/// as long.
+const TOAST_TICKS: u16 = 16;
+
+const SPINNER: [&str; 10] = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+
+pub struct Model {
+    pub running_state: RunningState,
+    pub time_range: TimeRange,
+    pub username: String,
+    pub limit: usize,
+    pub top_tracks: Vec<TopTracks>,
+    pub top_artists: Vec<TopArtists>,
+    pub top_albums: Vec<TopAlbums>,
+    pub release_eras: Vec<ReleaseEras>,
+    pub metrics: Vec<ListeningMetrics>,
+    pub report: Option<Report>,
+    pub report_page: usize,
+    pub toasts: Vec<Toast>,
+    pub last_refresh: Option<DateTime<Local>>,
+    /// Time ranges whose results are being fetched in the background.
+    pub loading: Vec<TimeRange>,
+    pub spinner: usize,
+    pub focus: Pane,
+    pub tracks_state: PaneState,
+    pub artists_state: PaneState,
+    pub albums_state: PaneState,
+    pub input_mode: InputMode,
+    pub search: Input,
+    pub detail: Option<Detail>,
+    pub track_details: HashMap<TrackId<'static>, Result<TrackDetails, String>>,
+    pub artist_details: HashMap<ArtistId<'static>, Result<ArtistDetails, String>>,
+    pub keymap: Keymap,
+    pub show_help: bool,
+    pub theme: Theme,
+    /// Where each tab was last drawn, for mouse clicks.
+    pub tab_areas: Vec<(Tab, Rect)>,
+    /// The pane whose scrollbar is being dragged.
+    pub dragging: Option<Pane>,
+    pub now_playing: Option<NowPlaying>,
+    /// Ticks until the playback state is polled again.
+    pub now_playing_poll: u16,
+    /// Whether a poll is in flight, so that slow responses don't pile up.
+    pub polling: bool,
+    /// Whether the last poll failed, so that a lasting failure is only
+    /// reported once.
+    pub poll_failed: bool,
+    /// Whether the terminal has focus. Polling pauses while it doesn't.
+    pub visible: bool,
+    pub devices: Option<DevicePicker>,
+    /// Where playback is sent, or the active device when `None`.
+    pub device: Option<PlaybackDevice>,
+    /// How the top tracks are saved as a playlist.
+    pub playlist_options: PlaylistOptions,
+    /// The playlist about to be written, shown for confirmation.
+    pub playlist_plan: Option<PlaylistPlan>,
+    pub discover: Option<Discover>,
+    /// Seeds picked for discovery, used instead of automatic ones.
+    pub seeds: Vec<Seed>,
+    pub discover_options: DiscoverOptions,
+    pub graph: Option<GraphView>,
+    pub graph_options: GraphOptions,
+    pub releases: Option<ReleasesView>,
+    pub release_options: ReleaseOptions,
+}
+
+impl Model {
+    pub fn new() -> Model {
+        Model {
+            running_state: RunningState::Running,
+            username: "None".to_string(),
+            limit: 10,
+            focus: Pane::Tracks,
+            tracks_state: PaneState::default(),
+            artists_state: PaneState::default(),
+            albums_state: PaneState::default(),
+            input_mode: InputMode::Normal,
+            search: Input::default(),
+            detail: None,
+            track_details: HashMap::new(),
+            artist_details: HashMap::new(),
+            keymap: Keymap::default(),
+            show_help: false,
+            theme: Theme::default(),
+            tab_areas: Vec::new(),
+            dragging: None,
+            now_playing: None,
+            now_playing_poll: 0,
+            polling: false,
+            poll_failed: false,
+            visible: true,
+            devices: None,
+            device: None,
+            playlist_options: PlaylistOptions::default(),
+            playlist_plan: None,
+            discover: None,
+            seeds: Vec::new(),
+            discover_options: DiscoverOptions::default(),
+            graph: None,
+            graph_options: GraphOptions::default(),
+            releases: None,
+            release_options: ReleaseOptions::default(),
+            top_tracks: Vec::new(),
+            top_artists: Vec::new(),
+            top_albums: Vec::new(),
+            release_eras: Vec::new(),
+            metrics: Vec::new(),
+            report: None,
+            report_page: 0,
+            toasts: Vec::new(),
+            last_refresh: None,
+            loading: Vec::new(),
+            spinner: 0,
+            time_range: TimeRange::ShortTerm,
+        }
+    }
+
+    /// Replaces the results of a single time range, keeping the per-range
+    /// lists in [`TIME_RANGES`] order.
+    pub fn set_range_results(&mut self, results: RangeResults) {
+        let time_range = results.time_range;
+        self.last_refresh = self.last_refresh.max(Some(results.fetched_at));
+        let order = |range: &TimeRange| TIME_RANGES.iter().position(|r| r == range);
+
+        self.top_tracks.retain(|t| t.time_range != time_range);
+        self.top_tracks.push(results.tracks);
+        self.top_tracks.sort_by_key(|t| order(&t.time_range));
+
+        self.top_artists.retain(|a| a.time_range != time_range);
+        self.top_artists.push(results.artists);
+        self.top_artists.sort_by_key(|a| order(&a.time_range));
+
+        self.top_albums.retain(|a| a.time_range != time_range);
+        self.top_albums.push(results.albums);
+        self.top_albums.sort_by_key(|a| order(&a.time_range));
+
+        self.release_eras.retain(|e| e.time_range != time_range);
+        self.release_eras.push(results.eras);
+        self.release_eras.sort_by_key(|e| order(&e.time_range));
+
+        self.metrics.retain(|m| m.time_range != time_range);
+        self.metrics.push(results.metrics);
+        self.metrics.sort_by_key(|m| order(&m.time_range));
+    }
+
+    pub fn is_loading(&self) -> bool {
+        self.loading.contains(&self.time_range)
+    }
+
+    pub fn spinner_symbol(&self) -> &'static str {
+        SPINNER[self.spinner % SPINNER.len()]
+    }
+
+    pub fn current_tracks(&self) -> Option<&TopTracks> {
+        self.top_tracks
+            .iter()
+            .find(|tracks| tracks.time_range == self.time_range)
+    }
+
+    pub fn current_artists(&self) -> Option<&TopArtists> {
+        self.top_artists
+            .iter()
+            .find(|artists| artists.time_range == self.time_range)
+    }
+
+    pub fn current_albums(&self) -> Option<&TopAlbums> {
+        self.top_albums
+            .iter()
+            .find(|albums| albums.time_range == self.time_range)
+    }
+
+    pub fn current_eras(&self) -> Option<&ReleaseEras> {
+        self.release_eras
+            .iter()
+            .find(|eras| eras.time_range == self.time_range)
+    }
+
+    pub fn current_metrics(&self) -> Option<&ListeningMetrics> {
+        self.metrics
+            .iter()
+            .find(|metrics| metrics.time_range == self.time_range)
+    }
+
+    /// The range after the current one, wrapping around to the first.
+    pub fn next_time_range(&self) -> TimeRange {
+        match self.time_range {
+            TimeRange::ShortTerm => TimeRange::MediumTerm,
+            TimeRange::MediumTerm => TimeRange::LongTerm,
+            TimeRange::LongTerm => TimeRange::ShortTerm,
+        }
+    }
+
+    pub fn set_time_range(&mut self, time_range: TimeRange) {
+        self.time_range = time_range;
+        self.tracks_state.select(0);
+        self.artists_state.select(0);
+        self.albums_state.select(0);
+    }
+
+    pub fn pane_state(&self, pane: Pane) -> &PaneState {
+        match pane {
+            Pane::Tracks => &self.tracks_state,
+            Pane::Artists => &self.artists_state,
+            Pane::Albums => &self.albums_state,
+        }
+    }
+
+    pub fn pane_state_mut(&mut self, pane: Pane) -> &mut PaneState {
+        match pane {
+            Pane::Tracks => &mut self.tracks_state,
+            Pane::Artists => &mut self.artists_state,
+            Pane::Albums => &mut self.albums_state,
+        }
+    }
+
+    pub fn focused_state_mut(&mut self) -> &mut PaneState {
+        self.pane_state_mut(self.focus)
+    }
+
+    fn selected_rank(&self, pane: Pane) -> Option<usize> {
+        let rows = self.sorted_rows(pane);
+
+        match rows.get(self.pane_state(pane).selected())?[0].key {
+            SortKey::Number(rank) => Some(rank as usize),
+            SortKey::Text(_) => None,
+        }
+    }
+
+    /// Opens the detail popup for the selected row of the focused pane.
+    ///
+    /// Returns a fetch request when the details aren't cached yet or failed
+    /// to load last time. Does nothing while a full screen view hides the
+    /// panes.
+    pub fn open_detail(&mut self) -> Option<DetailRequest> {
+        if self.view_is_open() {
+            return None;
+        }
+        let rank = self.selected_rank(self.focus)?;
+
+        let detail = match self.focus {
+            Pane::Tracks => {
+                let track = self.current_tracks()?.tracks.get(rank - 1)?;
+                Detail::Track(track.clone())
+            }
+            Pane::Artists => {
+                let artist = self.current_artists()?.artists.get(rank - 1)?;
+                Detail::Artist(artist.clone())
+            }
+            Pane::Albums => return None,
+        };
+
+        // Failed fetches are only kept to show the error, and are tried
+        // again on the next open.
+        self.track_details.retain(|_, details| details.is_ok());
+        self.artist_details.retain(|_, details| details.is_ok());
+        let request = match &detail {
+            Detail::Track(track) => track
+                .id
+                .clone()
+                .filter(|id| !self.track_details.contains_key(id))
+                .map(DetailRequest::Track),
+            Detail::Artist(artist) => (!self.artist_details.contains_key(&artist.id))
+                .then(|| DetailRequest::Artist(artist.id.clone())),
+        };
+
+        self.detail = Some(detail);
+        request
+    }
+
+    /// The selected track, when the top tracks or the recommendations are
+    /// focused.
+    pub fn selected_track_id(&self) -> Option<TrackId<'static>> {
+        if let Some(discover) = &self.discover {
+            return discover.selected_recommendation().map(|r| r.id.clone());
+        }
+        if self.focus != Pane::Tracks {
+            return None;
+        }
+        let rank = self.selected_rank(Pane::Tracks)?;
+        self.current_tracks()?.tracks.get(rank - 1)?.id.clone()
+    }
+
+    /// The tracks shown in the top tracks, or in the recommendations, in the
+    /// order they are shown.
+    pub fn visible_track_ids(&self) -> Vec<TrackId<'static>> {
+        if let Some(discover) = &self.discover {
+            let recommendations = discover.recommendations.iter().flatten();
+            return recommendations.map(|r| r.id.clone()).collect();
+        }
+        let Some(top_tracks) = self.current_tracks() else {
+            return Vec::new();
+        };
+
+        self.sorted_rows(Pane::Tracks)
+            .iter()
+            .filter_map(|row| match row[0].key {
+                SortKey::Number(rank) => top_tracks.tracks.get(rank as usize - 1)?.id.clone(),
+                SortKey::Text(_) => None,
+            })
+            .collect()
+    }
+
+    /// The ranges a track appears in, with its rank in each of them.
+    pub fn track_ranks(&self, track: &TopTrack) -> Vec<String> {
+        self.top_tracks
+            .iter()
+            .filter_map(|top_tracks| {
+                let found = top_tracks
+                    .tracks
+                    .iter()
+                    .find(|t| match (&t.id, &track.id) {
+                        (Some(a), Some(b)) => a == b,
+                        _ => t.track_name == track.track_name && t.artists == track.artists,
+                    })?;
+                Some(format!(
+                    "{} #{}",
+                    show_time_range(&top_tracks.time_range),
+                    found.index
+                ))
+            })
+            .collect()
+    }
+
+    /// The ranges an artist appears in, with their rank in each of them.
+    pub fn artist_ranks(&self, artist: &TopArtist) -> Vec<String> {
+        self.top_artists
+            .iter()
+            .filter_map(|top_artists| {
+                let found = top_artists.artists.iter().find(|a| a.id == artist.id)?;
+                Some(format!(
+                    "{} #{}",
+                    show_time_range(&top_artists.time_range),
+                    found.index
+                ))
+            })
+            .collect()
+    }
+
+    /// Names of the tracks by `artist` across all of the top track lists.
+    pub fn artist_top_tracks(&self, artist: &TopArtist) -> Vec<String> {
+        let mut tracks: Vec<String> = Vec::new();
+
+        for track in self.top_tracks.iter().flat_map(|t| t.tracks.iter()) {
+            if track.artists.contains(&artist.artist_name) && !tracks.contains(&track.track_name) {
+                tracks.push(track.track_name.clone());
+            }
+        }
+        tracks
+    }
+
+    /// The rank of the playing track in the current top tracks.
+    pub fn playing_rank(&self) -> Option<usize> {
+        let playing = self.now_playing.as_ref()?;
+        let track =
+            self.current_tracks()?
+                .tracks
+                .iter()
+                .find(|t| match (&t.id, &playing.track_id) {
+                    (Some(a), Some(b)) => a == b,
+                    _ => t.track_name == playing.name && t.artists == playing.artists,
+                })?;
+        Some(track.index)
+    }
+
+    fn matches_search<'a, I>(&self, fields: I) -> bool
+    where
+        I: IntoIterator<Item = &'a str>,
+    {
+        matches_any(fields, self.search.value())
+    }
+
+    /// Rows of the pane in rank order, one [`TableCell`] per column.
+    ///
+    /// Only rows matching the search query are included, with the matched
+    /// characters of their text cells marked for highlighting.
+    pub fn pane_rows(&self, pane: Pane) -> Vec<Vec<TableCell>> {
+        let mut rows = self.unfiltered_pane_rows(pane);
+
+        for cell in rows.iter_mut().flatten() {
+            if let SortKey::Text(_) = cell.key {
+                cell.highlights = fuzzy_match(&cell.text, self.search.value()).unwrap_or_default();
+            }
+        }
+        rows
+    }
+
+    fn unfiltered_pane_rows(&self, pane: Pane) -> Vec<Vec<TableCell>> {
+        match pane {
+            Pane::Tracks => self
+                .current_tracks()
+                .map(|top_tracks| {
+                    top_tracks
+                        .tracks
+                        .iter()
+                        .filter(|track| {
+                            self.matches_search(
+                                [track.track_name.as_str(), track.album_name.as_str()]
+                                    .into_iter()
+                                    .chain(track.artists.iter().map(|a| a.as_str())),
+                            )
+                        })
+                        .map(|track| {
+                            vec![
+                                TableCell::number(track.index as i64, track.index.to_string()),
+                                TableCell::text(track.track_name.clone()),
+                                TableCell::text(track.artists.join(", ")),
+                                TableCell::text(track.album_name.clone()),
+                                TableCell::number(track.duration_ms, track.duration.clone()),
+                                TableCell::number(
+                                    track.popularity as i64,
+                                    track.popularity.to_string(),
+                                ),
+                            ]
+                        })
+                        .collect()
+                })
+                .unwrap_or_default(),
+            Pane::Artists => self
+                .current_artists()
+                .map(|top_artists| {
+                    top_artists
+                        .artists
+                        .iter()
+                        .filter(|artist| {
+                            self.matches_search(
+                                [artist.artist_name.as_str()]
+                                    .into_iter()
+                                    .chain(artist.genres.iter().map(|g| g.as_str())),
+                            )
+                        })
+                        .map(|artist| {
+                            vec![
+                                TableCell::number(artist.index as i64, artist.index.to_string()),
+                                TableCell::text(artist.artist_name.clone()),
+                                TableCell::text(
+                                    artist
+                                        .genres
+                                        .iter()
+                                        .take(3)
+                                        .cloned()
+                                        .collect::<Vec<String>>()
+                                        .join(", "),
+                                ),
+                                TableCell::number(
+                                    artist.popularity as i64,
+                                    artist.popularity.to_string(),
+                                ),
+                            ]
+                        })
+                        .collect()
+                })
+                .unwrap_or_default(),
+            Pane::Albums => self
+                .current_albums()
+                .map(|top_albums| {
+                    top_albums
+                        .albums
+                        .iter()
+                        .filter(|album| {
+                            self.matches_search(
+                                [album.album_name.as_str()]
+                                    .into_iter()
+                                    .chain(album.artists.iter().map(|a| a.as_str())),
+                            )
+                        })
+                        .map(|album| {
+                            let year = match album.release_year {
+                                Some(year) => TableCell::number(year as i64, year.to_string()),
+                                None => TableCell::number(i64::MIN, "-".to_string()),
+                            };
+
+                            vec![
+                                TableCell::number(album.index as i64, album.index.to_string()),
+                                TableCell::text(album.album_name.clone()),
+                                TableCell::text(album.artists.join(", ")),
+                                year,
+                                TableCell::number(
+                                    album.track_count as i64,
+                                    album.track_count.to_string(),
+                                ),
+                            ]
+                        })
+                        .collect()
+                })
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Rows of the pane in the order chosen by its sort column.
+    ///
+    /// Rows that compare equal keep their rank order.
+    pub fn sorted_rows(&self, pane: Pane) -> Vec<Vec<TableCell>> {
+        let state = self.pane_state(pane);
+        let mut rows = self.pane_rows(pane);
+
+        rows.sort_by(|a, b| {
+            let ordering = a[state.sort_column].key.cmp(&b[state.sort_column].key);
+            if state.sort_descending {
+                ordering.reverse()
+            } else {
+                ordering
+            }
+        });
+        rows
+    }
+
+    /// Sorts the focused pane by `column`, reversing the order when it is
+    /// already sorted by that column. The selected row stays selected.
+    pub fn sort_focused(&mut self, column: usize) {
+        let pane = self.focus;
+        if column >= pane.columns().len() {
+            return;
+        }
+
+        let selected_rank = self
+            .sorted_rows(pane)
+            .get(self.pane_state(pane).selected())
+            .map(|row| row[0].key.clone());
+
+        let state = self.pane_state_mut(pane);
+        if state.sort_column == column {
+            state.sort_descending = !state.sort_descending;
+        } else {
+            state.sort_column = column;
+            state.sort_descending = false;
+        }
+
+        let position = self
+            .sorted_rows(pane)
+            .iter()
+            .position(|row| Some(&row[0].key) == selected_rank.as_ref())
+            .unwrap_or(0);
+        self.pane_state_mut(pane).select(position);
+    }
+
+    /// Whether the report, discovery, graph or releases view covers the
+    /// panes.
+    pub fn view_is_open(&self) -> bool {
+        self.report.is_some()
+            || self.discover.is_some()
+            || self.graph.is_some()
+            || self.releases.is_some()
+    }
+
+    /// The pane drawn at `position`, if the panes are on screen.
+    pub fn pane_at(&self, position: Position) -> Option<Pane> {
+        if self.view_is_open() {
+            return None;
+        }
+
+        [Pane::Tracks, Pane::Artists, Pane::Albums]
+            .into_iter()
+            .find(|pane| self.pane_state(*pane).area.contains(position))
+    }
+
+    pub fn tab_at(&self, position: Position) -> Option<Tab> {
+        self.tab_areas
+            .iter()
+            .find(|(_, area)| area.contains(position))
+            .map(|(tab, _)| *tab)
+    }
+
+    pub fn toggle_report(&mut self) {
+        self.discover = None;
+        self.graph = None;
+        self.releases = None;
+        self.report = match self.report {
+            Some(_) => None,
+            None => Some(get_report(
+                self.time_range,
+                &self.top_tracks,
+                &self.top_artists,
+                &self.top_albums,
+                &self.metrics,
+                &self.release_eras,
+            )),
+        };
+        self.report_page = 0;
+    }
+
+    /// The selected top track or artist as a seed for discovery.
+    fn selected_seed(&self) -> Option<Seed> {
+        let rank = self.selected_rank(self.focus)?;
+
+        match self.focus {
+            Pane::Tracks => {
+                let track = self.current_tracks()?.tracks.get(rank - 1)?;
+                Some(Seed::Track {
+                    id: track.id.clone()?,
+                    name: track.track_name.clone(),
+                })
+            }
+            Pane::Artists => {
+                let artist = self.current_artists()?.artists.get(rank - 1)?;
+                Some(Seed::Artist {
+                    id: artist.id.clone(),
+                    name: artist.artist_name.clone(),
+                })
+            }
+            Pane::Albums => None,
+        }
+    }
+
+    /// Picks the selected row as a seed, or drops it when it already is one.
+    pub fn toggle_seed(&mut self) {
+        let Some(seed) = self.selected_seed() else {
+            let message = "Select a top track or artist to seed from".to_string();
+            return self.notify(ToastKind::Error, message);
+        };
+
+        let message = if let Some(i) = self.seeds.iter().position(|s| *s == seed) {
+            self.seeds.remove(i);
+            format!("Removed {} from the seeds", seed.name())
+        } else if self.seeds.len() == MAX_SEEDS {
+            let message = format!("At most {} seeds can be picked", MAX_SEEDS);
+            return self.notify(ToastKind::Error, message);
+        } else {
+            let message = format!(
+                "Added {} to the seeds ({}/{})",
+                seed.name(),
+                self.seeds.len() + 1,
+                MAX_SEEDS
+            );
+            self.seeds.push(seed);
+            message
+        };
+        self.notify(ToastKind::Info, message);
+    }
+
+    /// Opens the discovery view with the picked seeds, or with automatic
+    /// ones when none were picked, returning the seeds to fetch for.
+    pub fn open_discover(&mut self) -> Option<Vec<Seed>> {
+        let seeds = if self.seeds.is_empty() {
+            auto_seeds(self.current_tracks()?, self.current_artists()?)
+        } else {
+            self.seeds.clone()
+        };
+        if seeds.is_empty() {
+            return None;
+        }
+
+        self.report = None;
+        self.graph = None;
+        self.releases = None;
+        self.discover = Some(Discover {
+            seeds: seeds.clone(),
+            recommendations: None,
+            selected: 0,
+        });
+        Some(seeds)
+    }
+
+    /// Every top track of every range, which recommendations leave out.
+    pub fn known_track_ids(&self) -> HashSet<TrackId<'static>> {
+        self.top_tracks
+            .iter()
+            .flat_map(|top_tracks| &top_tracks.tracks)
+            .filter_map(|track| track.id.clone())
+            .collect()
+    }
+
+    /// Opens the artist graph, returning the top artists of every range to
+    /// build it from and the names of the other artists of the top tracks.
+    pub fn open_graph(&mut self) -> Option<(Vec<GraphArtist>, HashSet<String>)> {
+        let top: Vec<GraphArtist> = self
+            .top_artists
+            .iter()
+            .flat_map(|top_artists| &top_artists.artists)
+            .map(GraphArtist::from)
+            .collect();
+        if top.is_empty() {
+            return None;
+        }
+        let listened = self
+            .top_tracks
+            .iter()
+            .flat_map(|top_tracks| &top_tracks.tracks)
+            .flat_map(|track| track.artists.iter().cloned())
+            .collect();
+
+        self.report = None;
+        self.discover = None;
+        self.releases = None;
+        self.graph = Some(GraphView::default());
+        Some((top, listened))
+    }
+
+    /// Opens the new releases view, returning the top artists of every range
+    /// to check and the albums of the top tracks, which are played already.
+    pub fn open_releases(&mut self) -> Option<(Vec<ArtistId<'static>>, HashSet<AlbumId<'static>>)> {
+        let mut artists: Vec<ArtistId<'static>> = Vec::new();
+        for artist in self.top_artists.iter().flat_map(|top| &top.artists) {
+            if !artists.contains(&artist.id) {
+                artists.push(artist.id.clone());
+            }
+        }
+        if artists.is_empty() {
+            return None;
+        }
+        let played = self
+            .top_tracks
+            .iter()
+            .flat_map(|top_tracks| &top_tracks.tracks)
+            .filter_map(|track| track.album_id.clone())
+            .collect();
+
+        self.report = None;
+        self.discover = None;
+        self.graph = None;
+        self.releases = Some(ReleasesView::default());
+        Some((artists, played))
+    }
+
+    /// Shows a transient message in the status bar.
+    pub fn notify(&mut self, kind: ToastKind, message: String) {
+        let ticks_left = match kind {
+            ToastKind::Info => TOAST_TICKS,
+            ToastKind::Error => TOAST_TICKS * 2,
+        };
+
+        self.toasts.push(Toast {
+            kind,
+            message,
+            ticks_left,
+        });
+    }
+}
+
+impl Default for Model {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ToastKind {
+    Info,
+    Error,
+}
+
+/// A transient message shown in the status bar until it runs out of ticks.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Toast {
+    pub kind: ToastKind,
+    pub message: String,
+    pub ticks_left: u16,
+}
+
+/// A clickable title in the tab bar.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tab {
+    Range(TimeRange),
+    Report,
+    Discover,
+    Graph,
+    Releases,
+    Pane(Pane),
+}
+
+/// The popup listing the devices playback can be sent to.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DevicePicker {
+    /// `None` while the devices are being fetched.
+    pub devices: Option<Vec<PlaybackDevice>>,
+    pub selected: usize,
+}
+
+impl DevicePicker {
+    pub fn select_next(&mut self) {
+        let count = self.devices.as_ref().map_or(0, |devices| devices.len());
+        self.selected = (self.selected + 1).min(count.saturating_sub(1));
+    }
+
+    pub fn select_previous(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    pub fn selected_device(&self) -> Option<&PlaybackDevice> {
+        self.devices.as_ref()?.get(self.selected)
+    }
+}
+
+/// The discovery view: tracks recommended from a few top artists and
+/// tracks.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Discover {
+    pub seeds: Vec<Seed>,
+    /// `None` while the recommendations are being fetched.
+    pub recommendations: Option<Vec<Recommendation>>,
+    pub selected: usize,
+}
+
+impl Discover {
+    pub fn select_next(&mut self) {
+        let count = self.recommendations.as_ref().map_or(0, |r| r.len());
+        self.selected = (self.selected + 1).min(count.saturating_sub(1));
+    }
+
+    pub fn select_previous(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    pub fn selected_recommendation(&self) -> Option<&Recommendation> {
+        self.recommendations.as_ref()?.get(self.selected)
+    }
+}
+
+/// The artist graph view, scrolling through the lines of its summary.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GraphView {
+    /// `None` while the graph is being built.
+    pub graph: Option<(ArtistGraph, GraphSummary)>,
+    pub scroll: usize,
+}
+
+impl GraphView {
+    pub fn scroll_down(&mut self) {
+        let count = self
+            .graph
+            .as_ref()
+            .map_or(0, |(_, summary)| summary.lines().len());
+        self.scroll = (self.scroll + 1).min(count.saturating_sub(1));
+    }
+
+    pub fn scroll_up(&mut self) {
+        self.scroll = self.scroll.saturating_sub(1);
+    }
+}
+
+/// The new releases of the top artists.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ReleasesView {
+    /// `None` while the releases are being fetched.
+    pub releases: Option<Vec<Release>>,
+    pub selected: usize,
+}
+
+impl ReleasesView {
+    pub fn select_next(&mut self) {
+        let count = self.releases.as_ref().map_or(0, |r| r.len());
+        self.selected = (self.selected + 1).min(count.saturating_sub(1));
+    }
+
+    pub fn select_previous(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+}
+
+/// The item shown in the detail popup.
+#[derive(Debug, Clone)]
+pub enum Detail {
+    Track(TopTrack),
+    Artist(TopArtist),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum DetailRequest {
+    Track(TrackId<'static>),
+    Artist(ArtistId<'static>),
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum InputMode {
+    #[default]
+    Normal,
+    Search,
+}
+
+#[derive(Debug, Default, PartialEq, Eq)]
+pub enum RunningState {
+    #[default]
+    Running,
+    Done,
+}
@@ -0,0 +1,276 @@
+use ratatui::{
+    layout::{Constraint, Margin, Position, Rect},
+    style::Style,
+    text::{Line, Span},
+    widgets::{ScrollbarState, TableState},
+};
+
+/// Width a `Fill` column needs before it is worth showing.
+const MIN_FILL_WIDTH: u16 = 10;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Pane {
+    #[default]
+    Tracks,
+    Artists,
+    Albums,
+}
+
+impl Pane {
+    pub fn next(self) -> Pane {
+        match self {
+            Pane::Tracks => Pane::Artists,
+            Pane::Artists => Pane::Albums,
+            Pane::Albums => Pane::Tracks,
+        }
+    }
+
+    pub fn previous(self) -> Pane {
+        match self {
+            Pane::Tracks => Pane::Albums,
+            Pane::Artists => Pane::Tracks,
+            Pane::Albums => Pane::Artists,
+        }
+    }
+
+    pub fn title(self) -> &'static str {
+        match self {
+            Pane::Tracks => "Top Tracks",
+            Pane::Artists => "Top Artists",
+            Pane::Albums => "Top Albums",
+        }
+    }
+
+    /// Columns in the order they are hidden when the pane gets too narrow.
+    /// The rank and the name are always shown.
+    fn hide_order(self) -> &'static [usize] {
+        match self {
+            Pane::Tracks => &[5, 3, 4, 2],
+            Pane::Artists => &[3, 2],
+            Pane::Albums => &[4, 3, 2],
+        }
+    }
+
+    /// Indexes of the columns that fit into `width`.
+    pub fn visible_columns(self, width: u16) -> Vec<usize> {
+        let min_width = |i: &usize| match self.columns()[*i].1 {
+            Constraint::Length(length) => length,
+            _ => MIN_FILL_WIDTH,
+        };
+        // Leave room for the highlight symbol and a space between columns.
+        let needed = |columns: &[usize]| -> u16 {
+            2 + columns.iter().map(|i| min_width(i) + 1).sum::<u16>()
+        };
+
+        let mut columns: Vec<usize> = (0..self.columns().len()).collect();
+        for hidden in self.hide_order() {
+            if needed(&columns) <= width {
+                break;
+            }
+            columns.retain(|i| i != hidden);
+        }
+        columns
+    }
+
+    pub fn columns(self) -> &'static [(&'static str, Constraint)] {
+        match self {
+            Pane::Tracks => &[
+                ("#", Constraint::Length(4)),
+                ("Title", Constraint::Fill(3)),
+                ("Artists", Constraint::Fill(2)),
+                ("Album", Constraint::Fill(2)),
+                ("Length", Constraint::Length(6)),
+                ("Pop", Constraint::Length(4)),
+            ],
+            Pane::Artists => &[
+                ("#", Constraint::Length(4)),
+                ("Artist", Constraint::Fill(2)),
+                ("Genres", Constraint::Fill(3)),
+                ("Pop", Constraint::Length(4)),
+            ],
+            Pane::Albums => &[
+                ("#", Constraint::Length(4)),
+                ("Album", Constraint::Fill(3)),
+                ("Artists", Constraint::Fill(2)),
+                ("Year", Constraint::Length(5)),
+                ("Tracks", Constraint::Length(6)),
+            ],
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum SortKey {
+    Number(i64),
+    Text(String),
+}
+
+/// A table cell with the text shown, the key it sorts by and the indices of
+/// the characters matched by the search query.
+#[derive(Debug, Clone)]
+pub struct TableCell {
+    pub text: String,
+    pub key: SortKey,
+    pub highlights: Vec<usize>,
+}
+
+impl TableCell {
+    pub fn number(key: i64, text: String) -> TableCell {
+        TableCell {
+            text,
+            key: SortKey::Number(key),
+            highlights: Vec::new(),
+        }
+    }
+
+    pub fn text(text: String) -> TableCell {
+        TableCell {
+            key: SortKey::Text(text.to_lowercase()),
+            text,
+            highlights: Vec::new(),
+        }
+    }
+
+    pub fn line(&self, highlight: Style) -> Line<'static> {
+        if self.highlights.is_empty() {
+            return Line::from(self.text.clone());
+        }
+
+        let spans: Vec<Span> = self
+            .text
+            .chars()
+            .enumerate()
+            .map(|(i, c)| {
+                if self.highlights.contains(&i) {
+                    Span::styled(c.to_string(), highlight)
+                } else {
+                    Span::raw(c.to_string())
+                }
+            })
+            .collect();
+        Line::from(spans)
+    }
+}
+
+/// Selection, scroll offset and sort order of a single pane.
+///
+/// `row_count` and `viewport` are refreshed on every draw, so the selection
+/// stays valid when the terminal is resized or the content changes.
+#[derive(Debug, Default, Clone)]
+pub struct PaneState {
+    pub table: TableState,
+    pub scrollbar: ScrollbarState,
+    pub row_count: usize,
+    pub viewport: usize,
+    pub sort_column: usize,
+    pub sort_descending: bool,
+    /// Where the pane was last drawn, including its border.
+    pub area: Rect,
+}
+
+impl PaneState {
+    fn page(&self) -> usize {
+        self.viewport.saturating_sub(1).max(1)
+    }
+
+    pub fn selected(&self) -> usize {
+        self.table.selected().unwrap_or(0)
+    }
+
+    pub fn select(&mut self, index: usize) {
+        let index = index.min(self.row_count.saturating_sub(1));
+
+        self.table.select((self.row_count > 0).then_some(index));
+        self.scrollbar = ScrollbarState::new(self.row_count)
+            .viewport_content_length(self.viewport)
+            .position(index);
+    }
+
+    /// Moves to the next row, wrapping around to the first one.
+    pub fn cycle_next(&mut self) {
+        if self.selected() + 1 >= self.row_count {
+            self.select(0);
+        } else {
+            self.select_next(1);
+        }
+    }
+
+    /// Moves to the previous row, wrapping around to the last one.
+    pub fn cycle_previous(&mut self) {
+        if self.selected() == 0 {
+            self.select(usize::MAX);
+        } else {
+            self.select_previous(1);
+        }
+    }
+
+    pub fn select_next(&mut self, amount: usize) {
+        self.select(self.selected().saturating_add(amount));
+    }
+
+    pub fn select_previous(&mut self, amount: usize) {
+        self.select(self.selected().saturating_sub(amount));
+    }
+
+    pub fn page_down(&mut self) {
+        self.select_next(self.page());
+    }
+
+    pub fn page_up(&mut self) {
+        self.select_previous(self.page());
+    }
+
+    /// The column the scrollbar is drawn in, minus its arrows.
+    fn scrollbar_track(&self) -> Rect {
+        let inner = self.area.inner(Margin {
+            vertical: 1,
+            horizontal: 1,
+        });
+
+        Rect {
+            x: inner.right().saturating_sub(1),
+            y: inner.y + 1,
+            width: 1,
+            height: inner.height.saturating_sub(2),
+        }
+    }
+
+    pub fn on_scrollbar(&self, position: Position) -> bool {
+        let track = self.scrollbar_track();
+        position.x == track.x && (track.y - 1..track.bottom() + 1).contains(&position.y)
+    }
+
+    /// Selects the row at the same relative height as `y` on the scrollbar.
+    pub fn drag_scrollbar(&mut self, y: u16) {
+        let track = self.scrollbar_track();
+        // A hidden pane has no track to drag along.
+        if track.height == 0 {
+            return;
+        }
+        let span = track.height.saturating_sub(1).max(1) as usize;
+        let offset = y.clamp(track.y, track.bottom().saturating_sub(1)) - track.y;
+        let last_row = self.row_count.saturating_sub(1);
+
+        self.select((offset as usize * last_row + span / 2) / span);
+    }
+
+    /// Selects the row drawn at `y`, if any.
+    pub fn click_row(&mut self, y: u16) {
+        // The border and the header come before the first row.
+        let first_row = self.area.y + 2;
+        if y < first_row || y >= self.area.bottom().saturating_sub(1) {
+            return;
+        }
+
+        let index = self.table.offset() + (y - first_row) as usize;
+        if index < self.row_count {
+            self.select(index);
+        }
+    }
+
+    pub fn resize(&mut self, row_count: usize, viewport: usize) {
+        self.row_count = row_count;
+        self.viewport = viewport;
+        self.select(self.selected());
+    }
+}
@@ -0,0 +1,978 @@
+use std::collections::HashSet;
+use std::path::PathBuf;
+
+use crossterm::event::{Event, KeyEvent, MouseButton, MouseEvent, MouseEventKind};
+use ratatui::layout::Position;
+use rspotify::model::{AlbumId, ArtistId, TimeRange, TrackId};
+use tui_input::backend::crossterm::EventHandler;
+
+use super::model::{DetailRequest, DevicePicker, InputMode, Model, RunningState, Tab, ToastKind};
+use crate::client::{show_time_range, ArtistDetails, NowPlaying, TrackDetails, TIME_RANGES};
+use crate::discover::{Recommendation, Seed};
+use crate::event::TICK_RATE;
+use crate::export::Export;
+use crate::graph::{ArtistGraph, GraphArtist};
+use crate::playback::{Playback, PlaybackDevice};
+use crate::playlist::{PlannedTrack, PlaylistOptions, PlaylistPlan, PlaylistTracks};
+use crate::releases::Release;
+use crate::results::RangeResults;
+
+/// How many rows the mouse wheel scrolls at a time.
+const WHEEL_ROWS: usize = 3;
+
+/// How many ticks pass between polls of the playback state, about five
+/// seconds.
+const POLL_TICKS: u16 = 20;
+/// How many ticks to wait for the player to catch up after a playback
+/// request before polling it.
+const PLAYBACK_POLL_TICKS: u16 = 3;
+
+/// Everything that can happen to the [`Model`]: user input, ticks and the
+/// results of background work.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Message {
+    // Navigation
+    ScrollUp,
+    ScrollDown,
+    PageUp,
+    PageDown,
+    ScrollTop,
+    ScrollBottom,
+    FocusNext,
+    FocusPrevious,
+    SortBy(usize),
+    NextTimeRange,
+    SetTimeRange(TimeRange),
+    // Search
+    StartSearch,
+    SearchInput(KeyEvent),
+    ConfirmSearch,
+    ClearSearch,
+    NextMatch,
+    PreviousMatch,
+    // Popups and views
+    OpenDetail,
+    CloseDetail,
+    ToggleReport,
+    NextPage,
+    PreviousPage,
+    ToggleHelp,
+    // Discovery
+    ToggleDiscover,
+    ToggleSeed,
+    RecommendationsLoaded(Result<Vec<Recommendation>, String>),
+    ToggleGraph,
+    GraphLoaded(Result<Box<ArtistGraph>, String>),
+    ToggleReleases,
+    ReleasesLoaded(Result<Vec<Release>, String>),
+    // Playback
+    PlaySelected,
+    PlayVisible,
+    QueueSelected,
+    TogglePause,
+    NextTrack,
+    PreviousTrack,
+    OpenDevices,
+    CloseDevices,
+    NextDevice,
+    PreviousDevice,
+    SelectDevice,
+    DevicesLoaded(Result<Vec<PlaybackDevice>, String>),
+    PlaybackSent(Playback, Result<(), String>),
+    // Playlists
+    SavePlaylist,
+    PlaylistPlanned(Result<Box<PlaylistPlan>, String>),
+    ConfirmPlaylist,
+    CancelPlaylist,
+    PlaylistSaved(Result<String, String>),
+    // Data loading
+    Refresh,
+    RangeLoaded(TimeRange, Result<Box<RangeResults>, String>),
+    UsernameLoaded(Result<String, String>),
+    TrackDetailsLoaded(TrackId<'static>, Result<TrackDetails, String>),
+    ArtistDetailsLoaded(ArtistId<'static>, Result<ArtistDetails, String>),
+    NowPlayingLoaded(Result<Option<NowPlaying>, String>),
+    // Export
+    ExportReport,
+    ExportHtml,
+    ExportCard,
+    Exported(Result<Vec<PathBuf>, String>),
+    // Terminal
+    Tick,
+    FocusGained,
+    FocusLost,
+    Mouse(MouseEvent),
+    Resize,
+    Quit,
+}
+
+/// Side effects requested by [`update`], carried out by the main loop, which
+/// reports their results back as messages.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Command {
+    /// Fetch every time range in the background.
+    LoadRanges,
+    FetchDetail(DetailRequest),
+    /// Fetch recommendations for each of the seeds.
+    FetchRecommendations(Vec<Seed>),
+    /// Build the artist graph from the top artists, leaving the named
+    /// artists out of the unexplored ones.
+    BuildGraph(Vec<GraphArtist>, HashSet<String>),
+    /// Fetch the new releases of the artists, leaving out the given albums,
+    /// which are played already.
+    FetchReleases(Vec<ArtistId<'static>>, HashSet<AlbumId<'static>>),
+    /// Poll what is playing on the user's active device.
+    FetchNowPlaying,
+    FetchDevices,
+    /// Send a request to the player of the chosen device.
+    Playback(Playback),
+    /// Work out how the tracks would be saved, for a preview.
+    PlanPlaylist(PlaylistTracks, PlaylistOptions),
+    SavePlaylist(Box<PlaylistPlan>),
+    Export(Export),
+}
+
+fn join_paths(paths: &[PathBuf]) -> String {
+    paths
+        .iter()
+        .map(|path| path.display().to_string())
+        .collect::<Vec<String>>()
+        .join(" and ")
+}
+
+/// Applies `msg` to the model, returning the side effect it needs, if any.
+///
+/// `update` never touches the terminal, the network or the file system, so
+/// it can be driven by tests with a plain [`Model`].
+pub fn update(model: &mut Model, msg: Message) -> Option<Command> {
+    let command = apply(model, msg);
+    // A drag ends when its pane loses the focus or gets covered.
+    if model.view_is_open() || model.dragging.is_some_and(|pane| pane != model.focus) {
+        model.dragging = None;
+    }
+    command
+}
+
+fn apply(model: &mut Model, msg: Message) -> Option<Command> {
+    match msg {
+        Message::ScrollDown => match (&mut model.discover, &mut model.graph, &mut model.releases) {
+            (Some(discover), _, _) => discover.select_next(),
+            (_, Some(graph), _) => graph.scroll_down(),
+            (_, _, Some(releases)) => releases.select_next(),
+            _ => model.focused_state_mut().select_next(1),
+        },
+        Message::ScrollUp => match (&mut model.discover, &mut model.graph, &mut model.releases) {
+            (Some(discover), _, _) => discover.select_previous(),
+            (_, Some(graph), _) => graph.scroll_up(),
+            (_, _, Some(releases)) => releases.select_previous(),
+            _ => model.focused_state_mut().select_previous(1),
+        },
+        Message::PageDown => model.focused_state_mut().page_down(),
+        Message::PageUp => model.focused_state_mut().page_up(),
+        Message::ScrollTop => model.focused_state_mut().select(0),
+        Message::ScrollBottom => model.focused_state_mut().select(usize::MAX),
+        Message::FocusNext => model.focus = model.focus.next(),
+        Message::FocusPrevious => model.focus = model.focus.previous(),
+        Message::SortBy(column) => model.sort_focused(column),
+        Message::NextTimeRange => {
+            return update(model, Message::SetTimeRange(model.next_time_range()))
+        }
+        Message::SetTimeRange(time_range) => {
+            model.set_time_range(time_range);
+            model.report = None;
+            model.discover = None;
+            model.graph = None;
+            model.releases = None;
+        }
+        Message::StartSearch => model.input_mode = InputMode::Search,
+        Message::SearchInput(key) => {
+            model.search.handle_event(&Event::Key(key));
+            model.tracks_state.select(0);
+            model.artists_state.select(0);
+            model.albums_state.select(0);
+        }
+        Message::ConfirmSearch => model.input_mode = InputMode::Normal,
+        Message::ClearSearch => {
+            model.input_mode = InputMode::Normal;
+            model.search.reset();
+        }
+        Message::NextMatch => model.focused_state_mut().cycle_next(),
+        Message::PreviousMatch => model.focused_state_mut().cycle_previous(),
+        Message::OpenDetail => return model.open_detail().map(Command::FetchDetail),
+        Message::CloseDetail => model.detail = None,
+        Message::ToggleReport => model.toggle_report(),
+        Message::NextPage => {
+            if let Some(report) = &model.report {
+                let last_page = report.sections().len() - 1;
+                model.report_page = model.report_page.saturating_add(1).min(last_page);
+            }
+        }
+        Message::PreviousPage => model.report_page = model.report_page.saturating_sub(1),
+        Message::ToggleHelp => model.show_help = !model.show_help,
+        Message::ToggleDiscover if model.discover.is_some() => model.discover = None,
+        Message::ToggleDiscover => match model.open_discover() {
+            Some(seeds) => return Some(Command::FetchRecommendations(seeds)),
+            None => model.notify(
+                ToastKind::Error,
+                "No top artists or tracks to seed from".to_string(),
+            ),
+        },
+        Message::ToggleSeed => model.toggle_seed(),
+        Message::RecommendationsLoaded(result) => match (&mut model.discover, result) {
+            (Some(discover), Ok(recommendations)) => {
+                discover.recommendations = Some(recommendations)
+            }
+            (_, Err(e)) => {
+                model.discover = None;
+                model.notify(
+                    ToastKind::Error,
+                    format!("Failed to fetch recommendations: {}", e),
+                );
+            }
+            (None, Ok(_)) => {}
+        },
+        Message::ToggleGraph if model.graph.is_some() => model.graph = None,
+        Message::ToggleGraph => match model.open_graph() {
+            Some((top, listened)) => return Some(Command::BuildGraph(top, listened)),
+            None => model.notify(ToastKind::Error, "No top artists to start from".to_string()),
+        },
+        Message::GraphLoaded(result) => match (&mut model.graph, result) {
+            (Some(view), Ok(graph)) => {
+                let summary = graph.summary();
+                view.graph = Some((*graph, summary));
+            }
+            (_, Err(e)) => {
+                model.graph = None;
+                model.notify(
+                    ToastKind::Error,
+                    format!("Failed to build the artist graph: {}", e),
+                );
+            }
+            (None, Ok(_)) => {}
+        },
+        Message::ToggleReleases if model.releases.is_some() => model.releases = None,
+        Message::ToggleReleases => match model.open_releases() {
+            Some((artists, played)) => return Some(Command::FetchReleases(artists, played)),
+            None => model.notify(ToastKind::Error, "No top artists to check".to_string()),
+        },
+        Message::ReleasesLoaded(result) => match (&mut model.releases, result) {
+            (Some(view), Ok(releases)) => view.releases = Some(releases),
+            (_, Err(e)) => {
+                model.releases = None;
+                model.notify(
+                    ToastKind::Error,
+                    format!("Failed to fetch new releases: {}", e),
+                );
+            }
+            (None, Ok(_)) => {}
+        },
+        Message::PlaySelected => match model.selected_track_id() {
+            Some(id) => return Some(Command::Playback(Playback::Play(vec![id]))),
+            None => model.notify(ToastKind::Error, "Select a top track to play".to_string()),
+        },
+        Message::PlayVisible => {
+            let ids = model.visible_track_ids();
+            if ids.is_empty() {
+                model.notify(ToastKind::Error, "No tracks to play".to_string());
+            } else {
+                return Some(Command::Playback(Playback::Play(ids)));
+            }
+        }
+        Message::QueueSelected => match model.selected_track_id() {
+            Some(id) => return Some(Command::Playback(Playback::Queue(id))),
+            None => model.notify(ToastKind::Error, "Select a top track to queue".to_string()),
+        },
+        Message::TogglePause => {
+            // Flipped right away so the panel responds before the next poll.
+            let playback = match &mut model.now_playing {
+                Some(now_playing) if now_playing.is_playing => {
+                    now_playing.is_playing = false;
+                    Playback::Pause
+                }
+                Some(now_playing) => {
+                    now_playing.is_playing = true;
+                    Playback::Resume
+                }
+                None => Playback::Resume,
+            };
+            return Some(Command::Playback(playback));
+        }
+        Message::NextTrack => return Some(Command::Playback(Playback::Next)),
+        Message::PreviousTrack => return Some(Command::Playback(Playback::Previous)),
+        Message::OpenDevices => {
+            model.devices = Some(DevicePicker::default());
+            return Some(Command::FetchDevices);
+        }
+        Message::CloseDevices => model.devices = None,
+        Message::NextDevice => {
+            if let Some(picker) = &mut model.devices {
+                picker.select_next();
+            }
+        }
+        Message::PreviousDevice => {
+            if let Some(picker) = &mut model.devices {
+                picker.select_previous();
+            }
+        }
+        Message::SelectDevice => {
+            let picker = model.devices.take();
+            if let Some(device) = picker.as_ref().and_then(|p| p.selected_device()) {
+                model.device = Some(device.clone());
+                return Some(Command::Playback(Playback::Transfer(device.clone())));
+            }
+        }
+        Message::DevicesLoaded(Ok(devices)) => {
+            if let Some(picker) = &mut model.devices {
+                picker.selected = devices.iter().position(|d| d.is_active).unwrap_or(0);
+                picker.devices = Some(devices);
+            }
+        }
+        Message::DevicesLoaded(Err(e)) => {
+            model.devices = None;
+            model.notify(ToastKind::Error, format!("Failed to fetch devices: {}", e));
+        }
+        Message::PlaybackSent(playback, result) => {
+            match result {
+                Ok(()) => model.notify(ToastKind::Info, playback.done_message()),
+                Err(e) => model.notify(ToastKind::Error, format!("Playback failed: {}", e)),
+            }
+            // Give the player a moment to catch up before showing its state.
+            model.now_playing_poll = model.now_playing_poll.min(PLAYBACK_POLL_TICKS);
+        }
+        Message::SavePlaylist => return save_playlist(model),
+        Message::PlaylistPlanned(Ok(plan)) => model.playlist_plan = Some(*plan),
+        Message::PlaylistPlanned(Err(e)) => {
+            model.notify(ToastKind::Error, format!("Failed to plan playlist: {}", e))
+        }
+        Message::ConfirmPlaylist => {
+            if let Some(plan) = model.playlist_plan.take() {
+                return Some(Command::SavePlaylist(Box::new(plan)));
+            }
+        }
+        Message::CancelPlaylist => model.playlist_plan = None,
+        Message::PlaylistSaved(Ok(message)) => model.notify(ToastKind::Info, message),
+        Message::PlaylistSaved(Err(e)) => {
+            model.notify(ToastKind::Error, format!("Failed to save playlist: {}", e))
+        }
+        Message::Refresh => {
+            model.loading = TIME_RANGES.to_vec();
+            return Some(Command::LoadRanges);
+        }
+        Message::RangeLoaded(time_range, results) => {
+            model.loading.retain(|range| *range != time_range);
+            match results {
+                Ok(results) => model.set_range_results(*results),
+                Err(e) => model.notify(
+                    ToastKind::Error,
+                    format!("Failed to load {}: {}", show_time_range(&time_range), e),
+                ),
+            }
+        }
+        Message::UsernameLoaded(Ok(username)) => model.username = username,
+        Message::UsernameLoaded(Err(e)) => {
+            model.notify(ToastKind::Error, format!("Failed to fetch user: {}", e))
+        }
+        Message::TrackDetailsLoaded(id, details) => {
+            model.track_details.insert(id, details);
+        }
+        Message::ArtistDetailsLoaded(id, details) => {
+            model.artist_details.insert(id, details);
+        }
+        Message::NowPlayingLoaded(result) => {
+            model.polling = false;
+            match result {
+                Ok(now_playing) => {
+                    model.now_playing = now_playing;
+                    model.poll_failed = false;
+                }
+                Err(e) => {
+                    model.now_playing = None;
+                    if !model.poll_failed {
+                        model.notify(ToastKind::Error, format!("Failed to fetch playback: {}", e));
+                    }
+                    model.poll_failed = true;
+                }
+            }
+        }
+        Message::ExportReport if model.report.is_some() => {
+            return Some(Command::Export(Export::Report))
+        }
+        Message::ExportReport if model.graph.is_some() => {
+            return Some(Command::Export(Export::Graph))
+        }
+        Message::ExportReport => {}
+        Message::ExportHtml => return Some(Command::Export(Export::Html)),
+        Message::ExportCard => return Some(Command::Export(Export::Card)),
+        Message::Exported(Ok(paths)) => model.notify(
+            ToastKind::Info,
+            format!("Exported to {}", join_paths(&paths)),
+        ),
+        Message::Exported(Err(e)) => {
+            model.notify(ToastKind::Error, format!("Export failed: {}", e))
+        }
+        Message::Tick => {
+            model.spinner = model.spinner.wrapping_add(1);
+            for toast in &mut model.toasts {
+                toast.ticks_left = toast.ticks_left.saturating_sub(1);
+            }
+            model.toasts.retain(|toast| toast.ticks_left > 0);
+            return on_tick_playback(model);
+        }
+        Message::FocusGained => {
+            model.visible = true;
+            // Catch up right away instead of showing a stale track.
+            model.now_playing_poll = 0;
+            return poll_now_playing(model);
+        }
+        Message::FocusLost => model.visible = false,
+        Message::Mouse(event) => return on_mouse(model, event),
+        // The layout is recomputed on every draw, but a drag can't survive
+        // the panes moving.
+        Message::Resize => model.dragging = None,
+        Message::Quit => model.running_state = RunningState::Done,
+    };
+    None
+}
+
+/// Plans saving the recommendations when they are shown, and the top
+/// tracks otherwise.
+fn save_playlist(model: &mut Model) -> Option<Command> {
+    let (tracks, options) = match &model.discover {
+        Some(discover) => {
+            let tracks = discover
+                .recommendations
+                .iter()
+                .flatten()
+                .map(|r| PlannedTrack {
+                    id: r.id.clone(),
+                    name: r.name.clone(),
+                    artists: r.artists.clone(),
+                });
+            let tracks = PlaylistTracks {
+                time_range: model.time_range,
+                tracks: tracks.collect(),
+                skipped: 0,
+            };
+            let options = PlaylistOptions {
+                name: model.discover_options.playlist_name.clone(),
+                ..model.playlist_options.clone()
+            };
+            (tracks, options)
+        }
+        None => {
+            let Some(top_tracks) = model.current_tracks() else {
+                model.notify(ToastKind::Error, "No tracks to save".to_string());
+                return None;
+            };
+            let tracks = PlaylistTracks::from_top_tracks(top_tracks);
+            (tracks, model.playlist_options.clone())
+        }
+    };
+
+    if tracks.tracks.is_empty() {
+        model.notify(ToastKind::Error, "No tracks to save".to_string());
+        return None;
+    }
+    Some(Command::PlanPlaylist(tracks, options))
+}
+
+/// Advances the progress of the playing track between polls, and polls
+/// when it is due or the track has ended.
+fn on_tick_playback(model: &mut Model) -> Option<Command> {
+    if let Some(now_playing) = model.now_playing.as_mut().filter(|n| n.is_playing) {
+        let ended = now_playing.progress_ms >= now_playing.duration_ms;
+        now_playing.progress_ms =
+            (now_playing.progress_ms + TICK_RATE.as_millis() as i64).min(now_playing.duration_ms);
+        if !ended && now_playing.progress_ms >= now_playing.duration_ms {
+            model.now_playing_poll = 0;
+        }
+    }
+
+    model.now_playing_poll = model.now_playing_poll.saturating_sub(1);
+    poll_now_playing(model)
+}
+
+fn poll_now_playing(model: &mut Model) -> Option<Command> {
+    if !model.visible || model.polling || model.now_playing_poll > 0 {
+        return None;
+    }
+    model.polling = true;
+    model.now_playing_poll = POLL_TICKS;
+    Some(Command::FetchNowPlaying)
+}
+
+fn on_mouse(model: &mut Model, event: MouseEvent) -> Option<Command> {
+    let position = Position::new(event.column, event.row);
+
+    match event.kind {
+        MouseEventKind::Down(MouseButton::Left) => {
+            if model.show_help {
+                return update(model, Message::ToggleHelp);
+            }
+            if model.devices.is_some() {
+                return update(model, Message::CloseDevices);
+            }
+            if model.playlist_plan.is_some() {
+                return update(model, Message::CancelPlaylist);
+            }
+            if model.detail.is_some() {
+                return update(model, Message::CloseDetail);
+            }
+
+            if let Some(tab) = model.tab_at(position) {
+                match tab {
+                    Tab::Range(time_range) => {
+                        return update(model, Message::SetTimeRange(time_range))
+                    }
+                    Tab::Report if model.report.is_none() => {
+                        return update(model, Message::ToggleReport)
+                    }
+                    Tab::Report => {}
+                    Tab::Discover if model.discover.is_none() => {
+                        return update(model, Message::ToggleDiscover)
+                    }
+                    Tab::Discover => {}
+                    Tab::Graph if model.graph.is_none() => {
+                        return update(model, Message::ToggleGraph)
+                    }
+                    Tab::Graph => {}
+                    Tab::Releases if model.releases.is_none() => {
+                        return update(model, Message::ToggleReleases)
+                    }
+                    Tab::Releases => {}
+                    Tab::Pane(pane) => model.focus = pane,
+                }
+                return None;
+            }
+
+            let pane = model.pane_at(position)?;
+            model.focus = pane;
+            if model.pane_state(pane).on_scrollbar(position) {
+                model.dragging = Some(pane);
+                model.pane_state_mut(pane).drag_scrollbar(position.y);
+            } else {
+                model.pane_state_mut(pane).click_row(position.y);
+            }
+        }
+        MouseEventKind::Drag(MouseButton::Left) => {
+            let pane = model.dragging?;
+            model.pane_state_mut(pane).drag_scrollbar(position.y);
+        }
+        MouseEventKind::Up(MouseButton::Left) => model.dragging = None,
+        MouseEventKind::ScrollDown if model.report.is_some() => {
+            return update(model, Message::NextPage)
+        }
+        MouseEventKind::ScrollUp if model.report.is_some() => {
+            return update(model, Message::PreviousPage)
+        }
+        MouseEventKind::ScrollDown => {
+            let pane = model.pane_at(position)?;
+            model.pane_state_mut(pane).select_next(WHEEL_ROWS);
+        }
+        MouseEventKind::ScrollUp => {
+            let pane = model.pane_at(position)?;
+            model.pane_state_mut(pane).select_previous(WHEEL_ROWS);
+        }
+        _ => {}
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Local;
+    use rspotify::model::UserId;
+
+    use super::*;
+    use crate::albums::get_top_albums;
+    use crate::app::model::Discover;
+    use crate::app::Pane;
+    use crate::client::{TopArtists, TopTrack, TopTracks};
+    use crate::eras::get_release_eras;
+    use crate::metrics::get_listening_metrics;
+    use crate::playlist::WriteMode;
+
+    fn results(time_range: TimeRange) -> Box<RangeResults> {
+        let tracks = TopTracks {
+            time_range,
+            tracks: Vec::new(),
+        };
+        let artists = TopArtists {
+            time_range,
+            artists: Vec::new(),
+        };
+        Box::new(RangeResults {
+            time_range,
+            albums: get_top_albums(&tracks),
+            eras: get_release_eras(&tracks, 2026),
+            metrics: get_listening_metrics(&tracks, &artists),
+            tracks,
+            artists,
+            fetched_at: Local::now(),
+        })
+    }
+
+    #[test]
+    fn scrolls_only_the_focused_pane() {
+        let mut model = Model::new();
+        model.tracks_state.resize(5, 3);
+        model.artists_state.resize(5, 3);
+
+        update(&mut model, Message::ScrollDown);
+        update(&mut model, Message::ScrollDown);
+        assert_eq!(model.tracks_state.selected(), 2);
+
+        update(&mut model, Message::FocusNext);
+        assert_eq!(model.focus, Pane::Artists);
+        update(&mut model, Message::ScrollBottom);
+        assert_eq!(model.artists_state.selected(), 4);
+        update(&mut model, Message::PageUp);
+        assert_eq!(model.artists_state.selected(), 2);
+        assert_eq!(model.tracks_state.selected(), 2);
+    }
+
+    #[test]
+    fn changing_the_range_resets_the_selection_and_closes_the_report() {
+        let mut model = Model::new();
+        model.tracks_state.resize(5, 3);
+        update(&mut model, Message::ScrollBottom);
+        update(&mut model, Message::ToggleReport);
+        assert!(model.report.is_some());
+
+        update(&mut model, Message::NextTimeRange);
+        assert_eq!(model.time_range, TimeRange::MediumTerm);
+        assert_eq!(model.tracks_state.selected(), 0);
+        assert!(model.report.is_none());
+
+        update(&mut model, Message::NextTimeRange);
+        update(&mut model, Message::NextTimeRange);
+        assert_eq!(model.time_range, TimeRange::ShortTerm);
+    }
+
+    #[test]
+    fn refreshing_loads_every_range_and_reports_failures() {
+        let mut model = Model::new();
+        assert_eq!(
+            update(&mut model, Message::Refresh),
+            Some(Command::LoadRanges)
+        );
+        assert_eq!(model.loading, TIME_RANGES);
+
+        update(
+            &mut model,
+            Message::RangeLoaded(TimeRange::ShortTerm, Ok(results(TimeRange::ShortTerm))),
+        );
+        assert!(model.current_tracks().is_some());
+        assert!(model.last_refresh.is_some());
+        assert!(model.toasts.is_empty());
+
+        update(
+            &mut model,
+            Message::RangeLoaded(TimeRange::MediumTerm, Err("timed out".to_string())),
+        );
+        assert_eq!(model.loading, [TimeRange::LongTerm]);
+        assert_eq!(model.toasts.len(), 1);
+        assert_eq!(model.toasts[0].kind, ToastKind::Error);
+        assert_eq!(
+            model.toasts[0].message,
+            "Failed to load Medium Term: timed out"
+        );
+    }
+
+    #[test]
+    fn reports_a_failed_username_fetch() {
+        let mut model = Model::new();
+        update(
+            &mut model,
+            Message::UsernameLoaded(Ok("fixture".to_string())),
+        );
+        assert_eq!(model.username, "fixture");
+
+        update(
+            &mut model,
+            Message::UsernameLoaded(Err("offline".to_string())),
+        );
+        assert_eq!(model.username, "fixture");
+        assert_eq!(model.toasts[0].message, "Failed to fetch user: offline");
+    }
+
+    fn mouse(kind: MouseEventKind, column: u16, row: u16) -> Message {
+        Message::Mouse(MouseEvent {
+            kind,
+            column,
+            row,
+            modifiers: crossterm::event::KeyModifiers::NONE,
+        })
+    }
+
+    #[test]
+    fn stops_dragging_when_the_pane_is_hidden_or_covered() {
+        let mut model = Model::new();
+        model.tracks_state.resize(50, 6);
+        model.tracks_state.area = ratatui::layout::Rect::new(0, 0, 20, 10);
+
+        update(
+            &mut model,
+            mouse(MouseEventKind::Down(MouseButton::Left), 18, 2),
+        );
+        assert_eq!(model.dragging, Some(Pane::Tracks));
+        update(
+            &mut model,
+            mouse(MouseEventKind::Drag(MouseButton::Left), 18, 7),
+        );
+        assert_eq!(model.tracks_state.selected(), 49);
+
+        // Drawing a layout without the pane leaves it no area.
+        model.tracks_state.area = ratatui::layout::Rect::default();
+        update(
+            &mut model,
+            mouse(MouseEventKind::Drag(MouseButton::Left), 18, 4),
+        );
+        assert_eq!(model.tracks_state.selected(), 49);
+
+        update(&mut model, Message::FocusNext);
+        assert_eq!(model.dragging, None);
+
+        model.focus = Pane::Tracks;
+        model.tracks_state.area = ratatui::layout::Rect::new(0, 0, 20, 10);
+        update(
+            &mut model,
+            mouse(MouseEventKind::Down(MouseButton::Left), 18, 2),
+        );
+        update(&mut model, Message::ToggleReport);
+        assert_eq!(model.dragging, None);
+    }
+
+    fn playing(progress_ms: i64, duration_ms: i64) -> NowPlaying {
+        NowPlaying {
+            track_id: None,
+            name: "Teardrop".to_string(),
+            artists: vec!["Massive Attack".to_string()],
+            device: "Laptop".to_string(),
+            is_playing: true,
+            progress_ms,
+            duration_ms,
+        }
+    }
+
+    #[test]
+    fn polls_on_the_first_tick_and_then_every_poll_ticks() {
+        let mut model = Model::new();
+        assert_eq!(
+            update(&mut model, Message::Tick),
+            Some(Command::FetchNowPlaying)
+        );
+        update(&mut model, Message::NowPlayingLoaded(Ok(None)));
+
+        for _ in 1..POLL_TICKS {
+            assert_eq!(update(&mut model, Message::Tick), None);
+        }
+        assert_eq!(
+            update(&mut model, Message::Tick),
+            Some(Command::FetchNowPlaying)
+        );
+    }
+
+    #[test]
+    fn does_not_poll_while_a_poll_is_in_flight() {
+        let mut model = Model::new();
+        update(&mut model, Message::Tick);
+
+        for _ in 0..POLL_TICKS * 2 {
+            assert_eq!(update(&mut model, Message::Tick), None);
+        }
+    }
+
+    #[test]
+    fn pauses_polling_while_hidden() {
+        let mut model = Model::new();
+        update(&mut model, Message::FocusLost);
+
+        for _ in 0..POLL_TICKS * 2 {
+            assert_eq!(update(&mut model, Message::Tick), None);
+        }
+        assert_eq!(
+            update(&mut model, Message::FocusGained),
+            Some(Command::FetchNowPlaying)
+        );
+    }
+
+    #[test]
+    fn advances_progress_and_polls_when_the_track_ends() {
+        let mut model = Model::new();
+        update(&mut model, Message::Tick);
+        let tick = TICK_RATE.as_millis() as i64;
+        update(
+            &mut model,
+            Message::NowPlayingLoaded(Ok(Some(playing(1000 - tick - 1, 1000)))),
+        );
+
+        assert_eq!(update(&mut model, Message::Tick), None);
+        assert_eq!(model.now_playing.as_ref().unwrap().progress_ms, 999);
+        assert_eq!(
+            update(&mut model, Message::Tick),
+            Some(Command::FetchNowPlaying)
+        );
+        assert_eq!(model.now_playing.as_ref().unwrap().progress_ms, 1000);
+    }
+
+    #[test]
+    fn reports_a_lasting_poll_failure_once() {
+        let mut model = Model::new();
+        for _ in 0..3 {
+            update(&mut model, Message::Tick);
+            update(
+                &mut model,
+                Message::NowPlayingLoaded(Err("missing scope".to_string())),
+            );
+            model.now_playing_poll = 0;
+        }
+        assert_eq!(model.toasts.len(), 1);
+    }
+
+    fn device(id: &str, is_active: bool) -> PlaybackDevice {
+        PlaybackDevice {
+            id: id.to_string(),
+            name: id.to_string(),
+            kind: "Speaker".to_string(),
+            is_active,
+        }
+    }
+
+    #[test]
+    fn pausing_updates_the_panel_before_the_player_answers() {
+        let mut model = Model::new();
+        model.now_playing = Some(playing(0, 1000));
+
+        assert_eq!(
+            update(&mut model, Message::TogglePause),
+            Some(Command::Playback(Playback::Pause))
+        );
+        assert!(!model.now_playing.as_ref().unwrap().is_playing);
+        assert_eq!(
+            update(&mut model, Message::TogglePause),
+            Some(Command::Playback(Playback::Resume))
+        );
+    }
+
+    #[test]
+    fn playing_needs_a_selected_track() {
+        let mut model = Model::new();
+
+        assert_eq!(update(&mut model, Message::PlaySelected), None);
+        assert_eq!(update(&mut model, Message::PlayVisible), None);
+        assert_eq!(model.toasts.len(), 2);
+    }
+
+    #[test]
+    fn picks_a_device_and_sends_playback_there() {
+        let mut model = Model::new();
+
+        assert_eq!(
+            update(&mut model, Message::OpenDevices),
+            Some(Command::FetchDevices)
+        );
+        let devices = vec![device("laptop", false), device("kitchen", true)];
+        update(&mut model, Message::DevicesLoaded(Ok(devices)));
+        // The active device is preselected.
+        update(&mut model, Message::PreviousDevice);
+
+        assert_eq!(
+            update(&mut model, Message::SelectDevice),
+            Some(Command::Playback(Playback::Transfer(device(
+                "laptop", false
+            ))))
+        );
+        assert_eq!(model.devices, None);
+        assert_eq!(model.device, Some(device("laptop", false)));
+    }
+
+    #[test]
+    fn saves_a_playlist_only_once_the_preview_is_confirmed() {
+        let mut model = Model::new();
+        assert_eq!(update(&mut model, Message::SavePlaylist), None);
+        assert_eq!(model.toasts.len(), 1);
+
+        let plan = PlaylistPlan {
+            user_id: UserId::from_id("user".to_string()).unwrap(),
+            name: "Top 0".to_string(),
+            existing: None,
+            public: false,
+            mode: WriteMode::Replace,
+            time_range: TimeRange::ShortTerm,
+            tracks: Vec::new(),
+            skipped: 0,
+        };
+        update(
+            &mut model,
+            Message::PlaylistPlanned(Ok(Box::new(plan.clone()))),
+        );
+        assert_eq!(model.playlist_plan.as_ref(), Some(&plan));
+
+        assert_eq!(
+            update(&mut model, Message::ConfirmPlaylist),
+            Some(Command::SavePlaylist(Box::new(plan)))
+        );
+        assert_eq!(model.playlist_plan, None);
+        assert_eq!(update(&mut model, Message::ConfirmPlaylist), None);
+    }
+
+    fn with_teardrop() -> Model {
+        let mut model = Model::new();
+        model.top_tracks = vec![TopTracks {
+            time_range: TimeRange::ShortTerm,
+            tracks: vec![TopTrack {
+                index: 1,
+                id: TrackId::from_id("teardrop".to_string()).ok(),
+                track_name: "Teardrop".to_string(),
+                duration: "5:30".to_string(),
+                duration_ms: 330_000,
+                artists: vec!["Massive Attack".to_string()],
+                album_id: None,
+                album_name: "Mezzanine".to_string(),
+                album_artists: vec!["Massive Attack".to_string()],
+                release_date: Some("1998".to_string()),
+                popularity: 70,
+            }],
+        }];
+        model
+    }
+
+    #[test]
+    fn opens_no_detail_under_a_full_screen_view() {
+        let mut model = with_teardrop();
+        model.discover = Some(Discover {
+            seeds: Vec::new(),
+            recommendations: None,
+            selected: 0,
+        });
+
+        assert_eq!(update(&mut model, Message::OpenDetail), None);
+        assert!(model.detail.is_none());
+
+        model.discover = None;
+        assert!(matches!(
+            update(&mut model, Message::OpenDetail),
+            Some(Command::FetchDetail(_))
+        ));
+        assert!(model.detail.is_some());
+    }
+
+    #[test]
+    fn fetches_failed_details_again_on_the_next_open() {
+        let mut model = with_teardrop();
+        let id = TrackId::from_id("teardrop".to_string()).unwrap();
+        let fetch = Some(Command::FetchDetail(DetailRequest::Track(id.clone())));
+
+        assert_eq!(update(&mut model, Message::OpenDetail), fetch);
+        update(
+            &mut model,
+            Message::TrackDetailsLoaded(id.clone(), Err("timed out".to_string())),
+        );
+        assert!(model.track_details[&id].is_err());
+
+        update(&mut model, Message::CloseDetail);
+        assert_eq!(update(&mut model, Message::OpenDetail), fetch);
+        assert!(!model.track_details.contains_key(&id));
+    }
+}
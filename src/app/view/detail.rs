@@ -0,0 +1,97 @@
+use ratatui::{
+    layout::Alignment,
+    text::{Line, Span},
+    widgets::{block::Title, Block, BorderType, Paragraph, Wrap},
+};
+
+use crate::app::{Detail, Model};
+
+pub fn widget(model: &Model) -> Paragraph<'_> {
+    let theme = &model.theme;
+    let Some(detail) = &model.detail else {
+        return Paragraph::default();
+    };
+
+    let field = |name: &str, value: String| -> Line {
+        Line::from(vec![
+            Span::styled(format!("{}: ", name), theme.index),
+            Span::styled(value, theme.value),
+        ])
+    };
+    let or_none = |values: Vec<String>| {
+        if values.is_empty() {
+            "None".to_string()
+        } else {
+            values.join(", ")
+        }
+    };
+
+    let (title, lines) = match detail {
+        Detail::Track(track) => {
+            let mut lines = vec![
+                field("Artists", track.artists.join(", ")),
+                field("Album", track.album_name.clone()),
+                field(
+                    "Released",
+                    track.release_date.clone().unwrap_or("Unknown".to_string()),
+                ),
+                field("Length", track.duration.clone()),
+                field("Popularity", format!("{}/100", track.popularity)),
+                field("Appears in", or_none(model.track_ranks(track))),
+                Line::default(),
+            ];
+
+            let features = track.id.as_ref().and_then(|id| model.track_details.get(id));
+            lines.extend(match features {
+                Some(Ok(features)) => vec![
+                    field("Danceability", format!("{:.2}", features.danceability)),
+                    field("Energy", format!("{:.2}", features.energy)),
+                    field("Valence", format!("{:.2}", features.valence)),
+                    field("Acousticness", format!("{:.2}", features.acousticness)),
+                    field(
+                        "Instrumentalness",
+                        format!("{:.2}", features.instrumentalness),
+                    ),
+                    field("Tempo", format!("{:.0} BPM", features.tempo)),
+                ],
+                Some(Err(e)) => vec![field("Audio features", format!("unavailable ({})", e))],
+                None if track.id.is_none() => {
+                    vec![field("Audio features", "unavailable".to_string())]
+                }
+                None => vec![field("Audio features", "loading...".to_string())],
+            });
+
+            (track.track_name.clone(), lines)
+        }
+        Detail::Artist(artist) => {
+            let related = match model.artist_details.get(&artist.id) {
+                Some(Ok(details)) => or_none(details.related_artists.clone()),
+                Some(Err(e)) => format!("unavailable ({})", e),
+                None => "loading...".to_string(),
+            };
+
+            let lines = vec![
+                field("Genres", or_none(artist.genres.clone())),
+                field("Followers", artist.followers.to_string()),
+                field("Popularity", format!("{}/100", artist.popularity)),
+                field("Appears in", or_none(model.artist_ranks(artist))),
+                field("Top tracks", or_none(model.artist_top_tracks(artist))),
+                Line::default(),
+                field("Related artists", related),
+            ];
+
+            (artist.artist_name.clone(), lines)
+        }
+    };
+
+    Paragraph::new(lines)
+        .block(
+            Block::bordered()
+                .border_type(BorderType::QuadrantInside)
+                .border_style(theme.border)
+                .title(Title::from(title))
+                .title_bottom(Line::from("Esc: close").right_aligned())
+                .title_alignment(Alignment::Center),
+        )
+        .wrap(Wrap { trim: true })
+}
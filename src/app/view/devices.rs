@@ -0,0 +1,53 @@
+use ratatui::{
+    layout::Alignment,
+    text::{Line, Span},
+    widgets::{block::Title, Block, BorderType, Paragraph},
+};
+
+use crate::app::Model;
+
+/// Lists the devices playback can be sent to, marking the active one.
+pub fn widget(model: &Model) -> Paragraph<'_> {
+    let theme = model.theme;
+    let picker = model.devices.as_ref();
+
+    let lines: Vec<Line> = match picker.and_then(|picker| picker.devices.as_ref()) {
+        None => vec![Line::styled("Loading devices...", theme.muted)],
+        Some(devices) if devices.is_empty() => vec![Line::styled(
+            "No devices found, open Spotify on one first",
+            theme.muted,
+        )],
+        Some(devices) => devices
+            .iter()
+            .enumerate()
+            .map(|(i, device)| {
+                let selected = picker.is_some_and(|picker| picker.selected == i);
+                let marker = if selected { "> " } else { "  " };
+                let name_style = if selected {
+                    theme.selected
+                } else {
+                    theme.value
+                };
+
+                let mut spans = vec![
+                    Span::styled(marker, theme.index),
+                    Span::styled(device.name.clone(), name_style),
+                    Span::styled(format!(" ({})", device.kind), theme.muted),
+                ];
+                if device.is_active {
+                    spans.push(Span::styled(" active", theme.success));
+                }
+                Line::from(spans)
+            })
+            .collect(),
+    };
+
+    Paragraph::new(lines).block(
+        Block::bordered()
+            .border_type(BorderType::QuadrantInside)
+            .border_style(theme.border)
+            .title(Title::from("Devices"))
+            .title_bottom(Line::from("Enter: play here · Esc: close").right_aligned())
+            .title_alignment(Alignment::Center),
+    )
+}
@@ -0,0 +1,102 @@
+use ratatui::{
+    layout::{Alignment, Constraint, Layout, Rect},
+    text::{Line, Span},
+    widgets::{block::Title, Block, BorderType, Cell, Paragraph, Row, Table, TableState},
+    Frame,
+};
+
+use crate::app::Model;
+
+/// Lists the recommendations with the seeds each came from, below the seeds
+/// and target audio features they were asked for with.
+pub fn render(model: &Model, frame: &mut Frame, area: Rect) {
+    let Some(discover) = &model.discover else {
+        return;
+    };
+    let theme = model.theme;
+
+    let block = Block::bordered()
+        .border_type(BorderType::QuadrantInside)
+        .border_style(theme.border)
+        .title(Title::from("Discover"))
+        .title_bottom(Line::from("p: play · s: save as playlist · D: close").right_aligned())
+        .title_alignment(Alignment::Center);
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let targets = model.discover_options.targets.features();
+    let [seeds_area, targets_area, _, list_area] = Layout::vertical([
+        Constraint::Length(1),
+        Constraint::Length(if targets.is_empty() { 0 } else { 1 }),
+        Constraint::Length(1),
+        Constraint::Fill(1),
+    ])
+    .areas(inner);
+
+    let picked = if model.seeds.is_empty() {
+        "Seeds"
+    } else {
+        "Picked seeds"
+    };
+    let mut seeds = vec![Span::styled(format!("{}: ", picked), theme.muted)];
+    for (i, seed) in discover.seeds.iter().enumerate() {
+        if i > 0 {
+            seeds.push(Span::styled(" · ", theme.muted));
+        }
+        seeds.push(Span::styled(seed.name().to_string(), theme.value));
+    }
+    frame.render_widget(Line::from(seeds), seeds_area);
+
+    let targets = targets
+        .iter()
+        .map(|(feature, value)| format!("{} {}", feature, value))
+        .collect::<Vec<String>>()
+        .join(", ");
+    frame.render_widget(
+        Line::styled(format!("Targets: {}", targets), theme.muted),
+        targets_area,
+    );
+
+    let message = match &discover.recommendations {
+        None => Some("Finding recommendations..."),
+        Some(recommendations) if recommendations.is_empty() => {
+            Some("Nothing new found, try picking other seeds")
+        }
+        Some(_) => None,
+    };
+    if let Some(message) = message {
+        frame.render_widget(
+            Paragraph::new(message).style(theme.muted).centered(),
+            list_area,
+        );
+        return;
+    }
+
+    let header = Row::new(["#", "Title", "Artists", "Because you like"]).style(theme.header);
+    let rows = discover
+        .recommendations
+        .iter()
+        .flatten()
+        .enumerate()
+        .map(|(i, recommendation)| {
+            Row::new([
+                Cell::new((i + 1).to_string()).style(theme.index),
+                Cell::new(recommendation.name.clone()),
+                Cell::new(recommendation.artists.join(", ")),
+                Cell::new(recommendation.seeds.join(", ")).style(theme.muted),
+            ])
+        });
+    let widths = [
+        Constraint::Length(3),
+        Constraint::Fill(3),
+        Constraint::Fill(2),
+        Constraint::Fill(2),
+    ];
+    let table = Table::new(rows, widths)
+        .header(header)
+        .highlight_style(theme.selected)
+        .highlight_symbol("> ");
+
+    let mut state = TableState::default().with_selected(Some(discover.selected));
+    frame.render_stateful_widget(table, list_area, &mut state);
+}
@@ -0,0 +1,51 @@
+use ratatui::{
+    layout::Alignment,
+    text::Line,
+    widgets::{block::Title, Bar, BarChart, BarGroup, Block, BorderType},
+};
+
+use crate::app::Model;
+
+pub fn widget(model: &Model) -> BarChart<'_> {
+    let (bars, summary): (Vec<Bar>, String) = match model.current_eras() {
+        Some(eras) => {
+            let bars = eras
+                .decades
+                .iter()
+                .map(|(decade, count)| {
+                    Bar::default()
+                        .value(*count as u64)
+                        .label(Line::from(format!("{}s", decade)))
+                })
+                .collect();
+            let median_year = match eras.median_year {
+                Some(year) => year.to_string(),
+                None => "-".to_string(),
+            };
+            let nostalgia = match eras.nostalgia_index {
+                Some(age) => format!("{:.1} yrs", age),
+                None => "-".to_string(),
+            };
+            (
+                bars,
+                format!("Median {} · Nostalgia {}", median_year, nostalgia),
+            )
+        }
+        None => (Vec::new(), "No data".to_string()),
+    };
+
+    BarChart::default()
+        .block(
+            Block::bordered()
+                .border_type(BorderType::QuadrantInside)
+                .border_style(model.theme.border)
+                .title(Title::from(format!("Release Decades ({})", summary)))
+                .title_alignment(Alignment::Center),
+        )
+        .data(BarGroup::default().bars(&bars))
+        .bar_width(5)
+        .bar_gap(1)
+        .bar_style(model.theme.chart)
+        .value_style(model.theme.chart_value)
+        .label_style(model.theme.value)
+}
@@ -0,0 +1,62 @@
+use ratatui::{
+    layout::{Alignment, Rect},
+    text::Line,
+    widgets::{block::Title, Block, BorderType, Paragraph},
+    Frame,
+};
+
+use crate::app::Model;
+
+/// Lists the clusters, bridge artists and unexplored neighbors of the artist
+/// graph.
+pub fn render(model: &Model, frame: &mut Frame, area: Rect) {
+    let Some(view) = &model.graph else {
+        return;
+    };
+    let theme = model.theme;
+
+    let block = Block::bordered()
+        .border_type(BorderType::QuadrantInside)
+        .border_style(theme.border)
+        .title(Title::from("Artist Graph"))
+        .title_bottom(Line::from("e: export as DOT and GraphML · A: close").right_aligned())
+        .title_alignment(Alignment::Center);
+
+    let Some((_, summary)) = &view.graph else {
+        let message = format!(
+            "Following related artists {} deep...",
+            model.graph_options.depth
+        );
+        frame.render_widget(
+            Paragraph::new(message)
+                .style(theme.muted)
+                .centered()
+                .block(block),
+            area,
+        );
+        return;
+    };
+
+    let lines: Vec<Line> = summary
+        .lines()
+        .into_iter()
+        .enumerate()
+        .map(|(i, line)| {
+            let style = if i == 0 {
+                theme.muted
+            } else if line.starts_with(' ') {
+                theme.value
+            } else {
+                theme.header
+            };
+            Line::styled(line, style)
+        })
+        .collect();
+
+    frame.render_widget(
+        Paragraph::new(lines)
+            .block(block)
+            .scroll((view.scroll as u16, 0)),
+        area,
+    );
+}
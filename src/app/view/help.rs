@@ -0,0 +1,48 @@
+use ratatui::{
+    layout::Alignment,
+    text::{Line, Span},
+    widgets::{block::Title, Block, BorderType, Paragraph},
+};
+
+use crate::app::Model;
+use crate::keymap::ACTIONS;
+
+/// Lists the bindings of the current keymap, followed by the keys that
+/// can't be rebound.
+pub fn widget(model: &Model) -> Paragraph<'_> {
+    let key_style = model.theme.index;
+    let width = ACTIONS
+        .iter()
+        .map(|action| model.keymap.describe(*action).chars().count())
+        .max()
+        .unwrap_or_default()
+        .max("esc/enter".len());
+
+    let line = |keys: String, description: &str| -> Line {
+        Line::from(vec![
+            Span::styled(format!("{:>width$}  ", keys), key_style),
+            Span::styled(description.to_string(), model.theme.value),
+        ])
+    };
+
+    let mut lines: Vec<Line> = ACTIONS
+        .iter()
+        .filter(|action| !model.keymap.keys(**action).is_empty())
+        .map(|action| line(model.keymap.describe(*action), action.description()))
+        .collect();
+    lines.extend(vec![
+        Line::default(),
+        line("1-9".to_string(), "Sort by column"),
+        line("enter".to_string(), "Confirm search"),
+        line("esc/enter".to_string(), "Close popup"),
+    ]);
+
+    Paragraph::new(lines).block(
+        Block::bordered()
+            .border_type(BorderType::QuadrantInside)
+            .border_style(model.theme.border)
+            .title(Title::from("Keys"))
+            .title_bottom(Line::from("Esc: close").right_aligned())
+            .title_alignment(Alignment::Center),
+    )
+}
@@ -0,0 +1,242 @@
+//! Components that draw the [`Model`], one per part of the screen.
+//!
+//! Besides recording where panes and tabs end up for mouse handling, views
+//! only read the model, so each can be rendered on its own.
+
+pub mod detail;
+pub mod devices;
+pub mod discover;
+pub mod eras;
+pub mod graph;
+pub mod help;
+pub mod now_playing;
+pub mod pane;
+pub mod playlist;
+pub mod releases;
+pub mod report;
+pub mod search;
+pub mod status;
+pub mod summary;
+pub mod tabs;
+
+#[cfg(test)]
+mod tests;
+
+use ratatui::{
+    layout::{Constraint, Direction, Flex, Layout, Rect},
+    text::{Line, Text},
+    widgets::{Clear, Paragraph},
+    Frame,
+};
+
+use super::{InputMode, Model, Pane};
+
+/// Below this size the TUI only shows a notice instead of garbled panes.
+const MIN_WIDTH: u16 = 40;
+const MIN_HEIGHT: u16 = 12;
+/// Below this size only the focused pane is shown.
+const NARROW_WIDTH: u16 = 80;
+const NARROW_HEIGHT: u16 = 24;
+/// From this width the three panes are shown side by side.
+const WIDE_WIDTH: u16 = 160;
+
+/// How the panes are arranged for the current terminal size.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LayoutMode {
+    TooSmall,
+    /// Only the focused pane, with a tab for each pane.
+    Narrow,
+    Normal,
+    /// The three panes side by side.
+    Wide,
+}
+
+impl LayoutMode {
+    pub fn for_area(area: Rect) -> LayoutMode {
+        if area.width < MIN_WIDTH || area.height < MIN_HEIGHT {
+            LayoutMode::TooSmall
+        } else if area.width < NARROW_WIDTH || area.height < NARROW_HEIGHT {
+            LayoutMode::Narrow
+        } else if area.width >= WIDE_WIDTH {
+            LayoutMode::Wide
+        } else {
+            LayoutMode::Normal
+        }
+    }
+}
+
+fn render_too_small(frame: &mut Frame) {
+    let area = frame.area();
+    let notice = Text::from(vec![
+        Line::from("Terminal too small"),
+        Line::from(format!(
+            "Need {}x{}, have {}x{}",
+            MIN_WIDTH, MIN_HEIGHT, area.width, area.height
+        )),
+    ]);
+    let [area] = Layout::vertical([Constraint::Length(2)])
+        .flex(Flex::Center)
+        .areas(area);
+
+    frame.render_widget(Paragraph::new(notice).centered(), area);
+}
+
+pub fn draw(model: &mut Model, frame: &mut Frame) {
+    let mode = LayoutMode::for_area(frame.area());
+    model.tab_areas.clear();
+    for pane in [Pane::Tracks, Pane::Artists, Pane::Albums] {
+        model.pane_state_mut(pane).area = Rect::default();
+    }
+
+    if mode == LayoutMode::TooSmall {
+        render_too_small(frame);
+        return;
+    }
+
+    let tabs_height = if mode == LayoutMode::Narrow { 2 } else { 1 };
+    let now_playing_height = match (&model.now_playing, mode) {
+        (None, _) => 0,
+        (Some(_), LayoutMode::Narrow) => now_playing::NARROW_HEIGHT,
+        (Some(_), _) => now_playing::HEIGHT,
+    };
+    let [tabs_area, main_area, now_playing_area, status_area] = Layout::vertical([
+        Constraint::Length(tabs_height),
+        Constraint::Fill(1),
+        Constraint::Length(now_playing_height),
+        Constraint::Length(1),
+    ])
+    .areas(frame.area());
+    let [range_tabs_area, pane_tabs_area] =
+        Layout::vertical([Constraint::Length(1), Constraint::Fill(1)]).areas(tabs_area);
+    tabs::render_ranges(model, frame, range_tabs_area);
+    now_playing::render(model, frame, now_playing_area);
+    status::render(model, frame, status_area);
+
+    if model.report.is_some() {
+        frame.render_widget(report::widget(model), main_area);
+        render_overlays(model, frame, mode);
+        return;
+    }
+    if model.discover.is_some() {
+        discover::render(model, frame, main_area);
+        render_overlays(model, frame, mode);
+        return;
+    }
+    if model.graph.is_some() {
+        graph::render(model, frame, main_area);
+        render_overlays(model, frame, mode);
+        return;
+    }
+    if model.releases.is_some() {
+        releases::render(model, frame, main_area);
+        render_overlays(model, frame, mode);
+        return;
+    }
+
+    let searching = model.input_mode == InputMode::Search || !model.search.value().is_empty();
+    let search_height = if searching { 1 } else { 0 };
+    let screen = Layout::new(
+        Direction::Vertical,
+        vec![Constraint::Fill(1), Constraint::Length(search_height)],
+    )
+    .split(main_area);
+
+    if searching {
+        search::render(model, frame, screen[1]);
+    }
+
+    match mode {
+        LayoutMode::Narrow => {
+            let focus = model.focus;
+            tabs::render_panes(model, frame, pane_tabs_area);
+            pane::render(model, frame, focus, screen[0]);
+        }
+        LayoutMode::Wide => {
+            let [top, bottom] =
+                Layout::vertical([Constraint::Fill(2), Constraint::Fill(1)]).areas(screen[0]);
+            let [tracks, artists, albums] = Layout::horizontal([
+                Constraint::Fill(3),
+                Constraint::Fill(2),
+                Constraint::Fill(2),
+            ])
+            .areas(top);
+            let [eras, summary] =
+                Layout::horizontal([Constraint::Fill(1), Constraint::Fill(1)]).areas(bottom);
+
+            pane::render(model, frame, Pane::Tracks, tracks);
+            pane::render(model, frame, Pane::Artists, artists);
+            pane::render(model, frame, Pane::Albums, albums);
+            frame.render_widget(eras::widget(model), eras);
+            frame.render_widget(summary::widget(model), summary);
+        }
+        _ => {
+            let layout = Layout::new(
+                Direction::Vertical,
+                vec![
+                    Constraint::Fill(1),
+                    Constraint::Fill(1),
+                    Constraint::Fill(1),
+                ],
+            )
+            .split(screen[0]);
+
+            let bottom = Layout::new(
+                Direction::Horizontal,
+                vec![
+                    Constraint::Fill(1),
+                    Constraint::Fill(1),
+                    Constraint::Fill(1),
+                ],
+            )
+            .split(layout[2]);
+
+            pane::render(model, frame, Pane::Tracks, layout[0]);
+            pane::render(model, frame, Pane::Artists, layout[1]);
+            pane::render(model, frame, Pane::Albums, bottom[0]);
+            frame.render_widget(eras::widget(model), bottom[1]);
+            frame.render_widget(summary::widget(model), bottom[2]);
+        }
+    }
+
+    if model.detail.is_some() {
+        let area = popup_area(frame.area(), mode);
+        frame.render_widget(Clear, area);
+        frame.render_widget(detail::widget(model), area);
+    }
+
+    render_overlays(model, frame, mode);
+}
+
+/// Draws the popups that can be open over any view, the report included.
+fn render_overlays(model: &Model, frame: &mut Frame, mode: LayoutMode) {
+    if model.devices.is_some() {
+        let area = popup_area(frame.area(), mode);
+        frame.render_widget(Clear, area);
+        frame.render_widget(devices::widget(model), area);
+    }
+    if model.playlist_plan.is_some() {
+        let area = popup_area(frame.area(), mode);
+        frame.render_widget(Clear, area);
+        frame.render_widget(playlist::widget(model), area);
+    }
+    if model.show_help {
+        let area = popup_area(frame.area(), mode);
+        frame.render_widget(Clear, area);
+        frame.render_widget(help::widget(model), area);
+    }
+}
+
+/// Centers a popup in `area`, using nearly all of it on narrow terminals.
+fn popup_area(area: Rect, mode: LayoutMode) -> Rect {
+    let (percent_x, percent_y) = if mode == LayoutMode::Narrow {
+        (100, 90)
+    } else {
+        (70, 80)
+    };
+
+    let vertical = Layout::vertical([Constraint::Percentage(percent_y)]).flex(Flex::Center);
+    let horizontal = Layout::horizontal([Constraint::Percentage(percent_x)]).flex(Flex::Center);
+    let [area] = vertical.areas(area);
+    let [area] = horizontal.areas(area);
+    area
+}
@@ -0,0 +1,85 @@
+use chrono::Duration;
+use ratatui::{
+    layout::{Alignment, Constraint, Layout, Rect},
+    text::{Line, Span},
+    widgets::{block::Title, Block, BorderType, Gauge, LineGauge},
+    Frame,
+};
+
+use crate::app::Model;
+use crate::client::{format_duration, NowPlaying};
+
+/// Rows taken up by the panel, or by the single line shown on narrow
+/// terminals.
+pub const HEIGHT: u16 = 4;
+pub const NARROW_HEIGHT: u16 = 1;
+
+fn ratio(now_playing: &NowPlaying) -> f64 {
+    if now_playing.duration_ms <= 0 {
+        return 0.0;
+    }
+    (now_playing.progress_ms as f64 / now_playing.duration_ms as f64).clamp(0.0, 1.0)
+}
+
+fn progress(now_playing: &NowPlaying) -> String {
+    format!(
+        "{} / {}",
+        format_duration(Duration::milliseconds(now_playing.progress_ms)),
+        format_duration(Duration::milliseconds(now_playing.duration_ms))
+    )
+}
+
+fn symbol(now_playing: &NowPlaying) -> &'static str {
+    if now_playing.is_playing {
+        "▶"
+    } else {
+        "⏸"
+    }
+}
+
+pub fn render(model: &Model, frame: &mut Frame, area: Rect) {
+    let Some(now_playing) = &model.now_playing else {
+        return;
+    };
+    let theme = model.theme;
+
+    if area.height < HEIGHT {
+        let widget = LineGauge::default()
+            .label(Line::from(vec![
+                Span::styled(format!("{} ", symbol(now_playing)), theme.playing),
+                Span::styled(now_playing.name.clone(), theme.value),
+                Span::styled(format!(" {} ", progress(now_playing)), theme.muted),
+            ]))
+            .filled_style(theme.playing)
+            .unfilled_style(theme.muted)
+            .ratio(ratio(now_playing));
+        frame.render_widget(widget, area);
+        return;
+    }
+
+    let block = Block::bordered()
+        .border_type(BorderType::QuadrantInside)
+        .border_style(theme.border_inactive)
+        .title(Title::from("Now Playing"))
+        .title_alignment(Alignment::Center);
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let [track_area, gauge_area] =
+        Layout::vertical([Constraint::Length(1), Constraint::Length(1)]).areas(inner);
+    let track = Line::from(vec![
+        Span::styled(format!("{} ", symbol(now_playing)), theme.playing),
+        Span::styled(now_playing.name.clone(), theme.value),
+        Span::styled(" - ", theme.muted),
+        Span::styled(now_playing.artists.join(", "), theme.value),
+        Span::styled(format!(" on {}", now_playing.device), theme.muted),
+    ]);
+    frame.render_widget(track, track_area);
+
+    let gauge = Gauge::default()
+        .gauge_style(theme.chart)
+        .use_unicode(true)
+        .label(progress(now_playing))
+        .ratio(ratio(now_playing));
+    frame.render_widget(gauge, gauge_area);
+}
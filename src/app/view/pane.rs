@@ -0,0 +1,115 @@
+use ratatui::{
+    layout::{Alignment, Constraint, Layout, Margin, Rect},
+    style::Style,
+    widgets::{block::Title, Block, BorderType, Cell, Paragraph, Row, Scrollbar, Table},
+    Frame,
+};
+
+use crate::app::{Model, Pane, SortKey};
+use crate::client::show_time_range;
+
+pub fn render(model: &mut Model, frame: &mut Frame, pane: Pane, area: Rect) {
+    let time_range = show_time_range(&model.time_range);
+    let loading = model.is_loading();
+    let spinner = if loading {
+        format!(" {}", model.spinner_symbol())
+    } else {
+        String::new()
+    };
+    let theme = model.theme;
+    let style = if model.focus == pane {
+        theme.border
+    } else {
+        theme.border_inactive
+    };
+
+    model.pane_state_mut(pane).area = area;
+    let rows = model.sorted_rows(pane);
+    let inner = area.inner(Margin {
+        vertical: 1,
+        horizontal: 1,
+    });
+    // One row of the inner area is taken up by the header.
+    model
+        .pane_state_mut(pane)
+        .resize(rows.len(), inner.height.saturating_sub(1) as usize);
+
+    // The scrollbar takes up the last column of the inner area.
+    let columns = pane.visible_columns(inner.width.saturating_sub(1));
+    let playing = match pane {
+        Pane::Tracks => model.playing_rank(),
+        _ => None,
+    };
+    let state = model.pane_state_mut(pane);
+    let header = Row::new(
+        pane.columns()
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| columns.contains(i))
+            .map(|(i, (title, _))| {
+                let indicator = match (i == state.sort_column, state.sort_descending) {
+                    (true, false) => " ▲",
+                    (true, true) => " ▼",
+                    (false, _) => "",
+                };
+                Cell::new(format!("{}{}", title, indicator))
+            }),
+    )
+    .style(theme.header);
+
+    let empty = rows.is_empty();
+    let rows = rows.into_iter().map(|row| {
+        let style = match row[0].key {
+            SortKey::Number(rank) if Some(rank as usize) == playing => theme.playing,
+            _ => Style::default(),
+        };
+        Row::new(
+            row.into_iter()
+                .enumerate()
+                .filter(|(i, _)| columns.contains(i))
+                .map(|(i, cell)| {
+                    if i == 0 {
+                        Cell::new(cell.text).style(theme.index)
+                    } else {
+                        Cell::new(cell.line(theme.matched))
+                    }
+                }),
+        )
+        .style(style)
+    });
+
+    let widths = columns.iter().map(|i| pane.columns()[*i].1);
+    let widget = Table::new(rows, widths)
+        .header(header)
+        .block(
+            Block::bordered()
+                .border_type(BorderType::QuadrantInside)
+                .border_style(style)
+                .title(Title::from(format!(
+                    "{} ({}){}",
+                    pane.title(),
+                    time_range,
+                    spinner
+                )))
+                .title_alignment(Alignment::Center),
+        )
+        .highlight_style(theme.selected)
+        .highlight_symbol("> ");
+
+    frame.render_stateful_widget(widget, area, &mut state.table);
+    frame.render_stateful_widget(Scrollbar::default(), inner, &mut state.scrollbar);
+
+    if empty {
+        let message = if loading {
+            "Loading..."
+        } else {
+            "Nothing here"
+        };
+        let [_, message_area] =
+            Layout::vertical([Constraint::Length(1), Constraint::Length(1)]).areas(inner);
+        frame.render_widget(
+            Paragraph::new(message).style(theme.muted).centered(),
+            message_area,
+        );
+    }
+}
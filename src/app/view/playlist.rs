@@ -0,0 +1,33 @@
+use ratatui::{
+    layout::Alignment,
+    text::Line,
+    widgets::{block::Title, Block, BorderType, Paragraph, Wrap},
+};
+
+use crate::app::Model;
+
+/// Previews the playlist the top tracks are about to be saved as.
+pub fn widget(model: &Model) -> Paragraph<'_> {
+    let theme = model.theme;
+    let lines: Vec<Line> = match &model.playlist_plan {
+        None => Vec::new(),
+        Some(plan) => plan
+            .preview()
+            .into_iter()
+            .enumerate()
+            .map(|(i, line)| match i {
+                0 => Line::styled(line, theme.value),
+                _ => Line::styled(line, theme.muted),
+            })
+            .collect(),
+    };
+
+    Paragraph::new(lines).wrap(Wrap { trim: false }).block(
+        Block::bordered()
+            .border_type(BorderType::QuadrantInside)
+            .border_style(theme.border)
+            .title(Title::from("Save Playlist"))
+            .title_bottom(Line::from("Enter: save · Esc: cancel").right_aligned())
+            .title_alignment(Alignment::Center),
+    )
+}
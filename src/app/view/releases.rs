@@ -0,0 +1,96 @@
+use ratatui::{
+    layout::{Alignment, Constraint, Layout, Rect},
+    text::Line,
+    widgets::{block::Title, Block, BorderType, Cell, Paragraph, Row, Table, TableState},
+    Frame,
+};
+
+use crate::app::Model;
+
+/// Lists the recent releases of the top artists, newest first, marking the
+/// ones not shown before.
+pub fn render(model: &Model, frame: &mut Frame, area: Rect) {
+    let Some(view) = &model.releases else {
+        return;
+    };
+    let theme = model.theme;
+
+    let block = Block::bordered()
+        .border_type(BorderType::QuadrantInside)
+        .border_style(theme.border)
+        .title(Title::from("New Releases"))
+        .title_bottom(Line::from("R: close").right_aligned())
+        .title_alignment(Alignment::Center);
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let [summary_area, _, list_area] = Layout::vertical([
+        Constraint::Length(1),
+        Constraint::Length(1),
+        Constraint::Fill(1),
+    ])
+    .areas(inner);
+
+    let days = model.release_options.days;
+    let Some(releases) = &view.releases else {
+        frame.render_widget(
+            Paragraph::new("Checking the releases of your top artists...")
+                .style(theme.muted)
+                .centered(),
+            list_area,
+        );
+        return;
+    };
+    if releases.is_empty() {
+        let message = format!("Nothing you haven't heard in the last {} days", days);
+        frame.render_widget(
+            Paragraph::new(message).style(theme.muted).centered(),
+            list_area,
+        );
+        return;
+    }
+
+    let new = releases.iter().filter(|release| release.new).count();
+    frame.render_widget(
+        Line::styled(
+            format!(
+                "{} releases in the last {} days not saved or played, {} new since last time",
+                releases.len(),
+                days,
+                new
+            ),
+            theme.muted,
+        ),
+        summary_area,
+    );
+
+    let header = Row::new(["", "Released", "Type", "Title", "Artists"]).style(theme.header);
+    let rows = releases.iter().map(|release| {
+        let (marker, style) = if release.new {
+            ("new", theme.value)
+        } else {
+            ("", theme.muted)
+        };
+        Row::new([
+            Cell::new(marker).style(theme.success),
+            Cell::new(release.release_date.to_string()).style(theme.index),
+            Cell::new(release.kind.clone()).style(theme.muted),
+            Cell::new(release.name.clone()).style(style),
+            Cell::new(release.artists.join(", ")),
+        ])
+    });
+    let widths = [
+        Constraint::Length(3),
+        Constraint::Length(10),
+        Constraint::Length(6),
+        Constraint::Fill(3),
+        Constraint::Fill(2),
+    ];
+    let table = Table::new(rows, widths)
+        .header(header)
+        .highlight_style(theme.selected)
+        .highlight_symbol("> ");
+
+    let mut state = TableState::default().with_selected(Some(view.selected));
+    frame.render_stateful_widget(table, list_area, &mut state);
+}
@@ -0,0 +1,66 @@
+use ratatui::{
+    layout::Alignment,
+    style::Style,
+    text::{Line, Span, Text},
+    widgets::{block::Title, Block, BorderType, Paragraph, Wrap},
+};
+
+use crate::app::Model;
+
+pub fn widget(model: &Model) -> Paragraph<'_> {
+    let theme = &model.theme;
+    let Some(report) = &model.report else {
+        return Paragraph::default();
+    };
+
+    let sections = report.sections();
+    let page = model.report_page.min(sections.len() - 1);
+    let section = &sections[page];
+
+    let mut lines = Text::default();
+    lines.extend(vec![
+        Line::from(Span::styled(section.title.clone(), theme.header)),
+        Line::default(),
+    ]);
+
+    for (i, line) in section.lines.iter().enumerate() {
+        let marker = if section.ordered {
+            format!("{}", i + 1)
+        } else {
+            "•".to_string()
+        };
+
+        let result = vec![
+            Span::styled(marker, theme.index),
+            Span::styled(" - ", Style::default()),
+            Span::styled(line.clone(), theme.value),
+        ];
+
+        let text: Vec<Line<'_>> = vec![result.into()];
+        lines.extend(text)
+    }
+
+    lines.extend(vec![
+        Line::default(),
+        Line::from(Span::styled(
+            "h/l: page · e: export · w: close",
+            theme.muted,
+        )),
+    ]);
+
+    Paragraph::new(lines)
+        .block(
+            Block::bordered()
+                .border_type(BorderType::QuadrantInside)
+                .border_style(theme.border)
+                .title(Title::from(format!(
+                    "{} ({}/{})",
+                    report.title(),
+                    page + 1,
+                    sections.len()
+                )))
+                .title_alignment(Alignment::Center),
+        )
+        .wrap(Wrap { trim: true })
+        .centered()
+}
@@ -0,0 +1,51 @@
+use ratatui::{
+    layout::Rect,
+    text::{Line, Span},
+    widgets::Paragraph,
+    Frame,
+};
+
+use crate::app::{InputMode, Model, Pane};
+
+pub fn render(model: &Model, frame: &mut Frame, area: Rect) {
+    let counts = [Pane::Tracks, Pane::Artists, Pane::Albums]
+        .iter()
+        .map(|pane| {
+            format!(
+                "{} {}",
+                model.pane_rows(*pane).len(),
+                pane.title().trim_start_matches("Top ").to_lowercase()
+            )
+        })
+        .collect::<Vec<String>>()
+        .join(", ");
+
+    let prefix = "/";
+    let width = area
+        .width
+        .saturating_sub(prefix.len() as u16 + counts.len() as u16 + 3);
+    let scroll = model.search.visual_scroll(width as usize);
+
+    let line = Line::from(vec![
+        Span::styled(prefix, model.theme.index),
+        Span::raw(
+            model
+                .search
+                .value()
+                .chars()
+                .skip(scroll)
+                .collect::<String>(),
+        ),
+    ]);
+    frame.render_widget(Paragraph::new(line), area);
+    frame.render_widget(
+        Paragraph::new(Line::from(format!("({})", counts)).right_aligned())
+            .style(model.theme.muted),
+        area,
+    );
+
+    if model.input_mode == InputMode::Search {
+        let cursor = model.search.visual_cursor().saturating_sub(scroll) as u16;
+        frame.set_cursor_position((area.x + prefix.len() as u16 + cursor, area.y));
+    }
+}
@@ -0,0 +1,43 @@
+use ratatui::{
+    layout::Rect,
+    text::{Line, Span},
+    widgets::Paragraph,
+    Frame,
+};
+
+use crate::app::{Model, ToastKind};
+use crate::client::show_time_range;
+use crate::keymap::Action;
+
+pub fn render(model: &Model, frame: &mut Frame, area: Rect) {
+    let refreshed = match (&model.last_refresh, model.is_loading()) {
+        (_, true) => format!("refreshing {}", model.spinner_symbol()),
+        (Some(time), false) => format!("refreshed {}", time.format("%H:%M:%S")),
+        (None, false) => "not loaded".to_string(),
+    };
+
+    let status = Line::from(vec![
+        Span::styled(format!(" {} ", model.username), model.theme.selected),
+        Span::raw(format!(
+            " {} · {}",
+            show_time_range(&model.time_range),
+            refreshed
+        )),
+        Span::styled(
+            format!(" · {}: help", model.keymap.describe(Action::Help)),
+            model.theme.muted,
+        ),
+    ]);
+    frame.render_widget(Paragraph::new(status), area);
+
+    if let Some(toast) = model.toasts.last() {
+        let style = match toast.kind {
+            ToastKind::Info => model.theme.success,
+            ToastKind::Error => model.theme.error,
+        };
+        frame.render_widget(
+            Paragraph::new(Line::from(format!("{} ", toast.message)).right_aligned()).style(style),
+            area,
+        );
+    }
+}
@@ -0,0 +1,87 @@
+use ratatui::{
+    layout::Alignment,
+    style::Style,
+    text::{Line, Span, Text},
+    widgets::{block::Title, Block, BorderType, Paragraph, Wrap},
+};
+
+use crate::app::Model;
+use crate::client::show_time_range;
+
+pub fn widget(model: &Model) -> Paragraph<'_> {
+    let time_range = show_time_range(&model.time_range);
+    let output = metrics_text(model);
+    let widget = Paragraph::new(output)
+        .block(
+            Block::bordered()
+                .border_type(BorderType::QuadrantInside)
+                .border_style(model.theme.border)
+                .title(Title::from(format!("Summary ({})", time_range)))
+                .title_alignment(Alignment::Center),
+        )
+        .wrap(Wrap { trim: true });
+    widget
+}
+
+pub fn metrics_text(model: &Model) -> Text<'_> {
+    let mut lines = Text::default();
+    let Some(metrics) = model.current_metrics() else {
+        return lines;
+    };
+
+    let rows = [
+        (
+            "Artist diversity",
+            metrics.artist_entropy,
+            "how evenly tracks spread across artists (0-1)",
+        ),
+        (
+            "Artist inequality",
+            metrics.artist_gini,
+            "Gini over artists, higher means a few dominate (0-1)",
+        ),
+        (
+            "Genre diversity",
+            metrics.genre_entropy,
+            "how evenly artists spread across genres (0-1)",
+        ),
+        (
+            "Genre inequality",
+            metrics.genre_gini,
+            "Gini over genres, higher means a few dominate (0-1)",
+        ),
+        (
+            "Top-3 concentration",
+            metrics.artist_concentration.map(|share| share * 100.0),
+            "% of top tracks by your three biggest artists",
+        ),
+        (
+            "Track obscurity",
+            metrics.track_obscurity,
+            "100 minus average track popularity",
+        ),
+        (
+            "Artist obscurity",
+            metrics.artist_obscurity,
+            "100 minus average artist popularity",
+        ),
+    ];
+
+    for (name, value, explanation) in rows {
+        let value = match value {
+            Some(value) if value > 1.0 => format!("{:.0}", value),
+            Some(value) => format!("{:.2}", value),
+            None => "-".to_string(),
+        };
+
+        let result = vec![
+            Span::styled(format!("{}: ", name), model.theme.index),
+            Span::styled(value, model.theme.value),
+            Span::styled(format!(" - {}", explanation), Style::default()),
+        ];
+
+        let text: Vec<Line<'_>> = vec![result.into()];
+        lines.extend(text)
+    }
+    lines
+}
@@ -0,0 +1,89 @@
+use ratatui::{
+    layout::Rect,
+    text::{Line, Span},
+    widgets::Paragraph,
+    Frame,
+};
+
+use super::NARROW_WIDTH;
+use crate::app::{Model, Pane, Tab};
+use crate::client::{show_time_range, TIME_RANGES};
+
+/// Draws the tab titles in a row, remembering where each one went.
+fn render_tabs(model: &mut Model, frame: &mut Frame, area: Rect, tabs: Vec<(Tab, String, bool)>) {
+    // Tight separators leave room for every tab on the smallest terminals.
+    let separator = if area.width < NARROW_WIDTH {
+        "│"
+    } else {
+        " │ "
+    };
+    let mut spans = Vec::new();
+    let mut x = area.x;
+    for (i, (tab, title, active)) in tabs.into_iter().enumerate() {
+        if i > 0 {
+            spans.push(Span::styled(separator, model.theme.muted));
+            x += separator.chars().count() as u16;
+        }
+
+        let title = format!(" {} ", title);
+        let width = title.chars().count() as u16;
+        let style = if active {
+            model.theme.selected
+        } else {
+            model.theme.value
+        };
+        spans.push(Span::styled(title, style));
+        model
+            .tab_areas
+            .push((tab, Rect::new(x, area.y, width, 1).intersection(area)));
+        x += width;
+    }
+
+    frame.render_widget(Paragraph::new(Line::from(spans)), area);
+}
+
+pub fn render_ranges(model: &mut Model, frame: &mut Frame, area: Rect) {
+    // "Short Term │ Medium Term │ ..." doesn't fit on the smallest terminals.
+    let short = area.width < NARROW_WIDTH;
+    let tabs = TIME_RANGES
+        .iter()
+        .map(|time_range| {
+            let active = model.report.is_none()
+                && model.discover.is_none()
+                && model.graph.is_none()
+                && model.releases.is_none()
+                && model.time_range == *time_range;
+            let title = show_time_range(time_range);
+            let title = if short {
+                title.trim_end_matches(" Term").to_string()
+            } else {
+                title
+            };
+            (Tab::Range(*time_range), title, active)
+        })
+        .chain([
+            (Tab::Report, "Wrapped".to_string(), model.report.is_some()),
+            (
+                Tab::Discover,
+                "Discover".to_string(),
+                model.discover.is_some(),
+            ),
+            (Tab::Graph, "Graph".to_string(), model.graph.is_some()),
+            (Tab::Releases, "New".to_string(), model.releases.is_some()),
+        ])
+        .collect();
+
+    render_tabs(model, frame, area, tabs);
+}
+
+pub fn render_panes(model: &mut Model, frame: &mut Frame, area: Rect) {
+    let tabs = [Pane::Tracks, Pane::Artists, Pane::Albums]
+        .into_iter()
+        .map(|pane| {
+            let title = pane.title().trim_start_matches("Top ").to_string();
+            (Tab::Pane(pane), title, model.focus == pane)
+        })
+        .collect();
+
+    render_tabs(model, frame, area, tabs);
+}
@@ -0,0 +1,681 @@
+//! Golden-buffer snapshots of the views, rendered headlessly from a fixture
+//! [`Model`], and golden files of the HTML exports of the same fixtures.
+//!
+//! Each snapshot is the text of the rendered buffer or the exported file,
+//! stored under `src/app/view/snapshots/`. After an intended change to a view, rerun the
+//! tests with `UPDATE_SNAPSHOTS=1` and review the diff of the snapshots.
+
+use std::collections::HashSet;
+use std::path::PathBuf;
+
+use ratatui::{backend::TestBackend, layout::Rect, style::Modifier, Frame, Terminal};
+use rspotify::model::{AlbumId, ArtistId, TimeRange, TrackId, UserId};
+
+use super::{draw, now_playing, pane};
+use crate::albums::get_top_albums;
+use crate::app::{update, Command, DevicePicker, Message, Model, Pane};
+use crate::client::{NowPlaying, TopArtist, TopArtists, TopTrack, TopTracks};
+use crate::discover::{Recommendation, Seed};
+use crate::eras::get_release_eras;
+use crate::export::{html, Export};
+use crate::graph::{ArtistGraph, GraphArtist};
+use crate::metrics::get_listening_metrics;
+use crate::playback::PlaybackDevice;
+use crate::playlist::{PlannedTrack, PlaylistPlan, WriteMode};
+use crate::releases::{parse_release_date, Release};
+
+const CURRENT_YEAR: i32 = 2026;
+
+fn track(index: usize, name: &str, artists: &[&str], album: &str, year: &str) -> TopTrack {
+    TopTrack {
+        index,
+        id: None,
+        track_name: name.to_string(),
+        duration: "3:25".to_string(),
+        duration_ms: 205_000,
+        artists: artists.iter().map(|a| a.to_string()).collect(),
+        album_id: None,
+        album_name: album.to_string(),
+        album_artists: vec![artists[0].to_string()],
+        release_date: Some(year.to_string()),
+        popularity: 80 - index as u32,
+    }
+}
+
+fn artist(index: usize, id: &str, name: &str, genres: &[&str]) -> TopArtist {
+    TopArtist {
+        index,
+        id: ArtistId::from_id(id.to_string()).unwrap(),
+        artist_name: name.to_string(),
+        genres: genres.iter().map(|g| g.to_string()).collect(),
+        popularity: 75 - index as u32,
+        followers: 1_000_000 / index as u32,
+    }
+}
+
+/// A model holding the given short term results, with everything derived
+/// from them filled in as a fetch would.
+fn fixture(tracks: Vec<TopTrack>, artists: Vec<TopArtist>) -> Model {
+    let tracks = TopTracks {
+        time_range: TimeRange::ShortTerm,
+        tracks,
+    };
+    let artists = TopArtists {
+        time_range: TimeRange::ShortTerm,
+        artists,
+    };
+
+    let mut model = Model::new();
+    model.username = "fixture".to_string();
+    model.top_albums = vec![get_top_albums(&tracks)];
+    model.release_eras = vec![get_release_eras(&tracks, CURRENT_YEAR)];
+    model.metrics = vec![get_listening_metrics(&tracks, &artists)];
+    model.top_tracks = vec![tracks];
+    model.top_artists = vec![artists];
+    model
+}
+
+fn sample_model() -> Model {
+    fixture(
+        vec![
+            track(1, "Paranoid Android", &["Radiohead"], "OK Computer", "1997"),
+            track(2, "Hyperballad", &["Björk"], "Post", "1995"),
+            track(3, "Windowlicker", &["Aphex Twin"], "Windowlicker", "1999"),
+            track(4, "Teardrop", &["Massive Attack"], "Mezzanine", "1998"),
+            track(
+                5,
+                "Everything In Its Right Place",
+                &["Radiohead"],
+                "Kid A",
+                "2000-10-02",
+            ),
+        ],
+        vec![
+            artist(
+                1,
+                "4Z8W4fKeB5YxbusRsdQVPb",
+                "Radiohead",
+                &["art rock", "alternative rock"],
+            ),
+            artist(
+                2,
+                "7w29UYBi0qsHi5RTcv3lmA",
+                "Björk",
+                &["art pop", "electronica"],
+            ),
+            artist(
+                3,
+                "6kBDZFXuLrZgHnvmPu9NsG",
+                "Aphex Twin",
+                &["idm", "ambient"],
+            ),
+            artist(4, "6FXMGgJwohJLUSr5nVlf9X", "Massive Attack", &["trip hop"]),
+        ],
+    )
+}
+
+fn long_names_model() -> Model {
+    fixture(
+        vec![
+            track(
+                1,
+                "Symphony No. 9 in D Minor, Op. 125 \"Choral\": IV. Presto - Allegro assai - Allegro molto assai (Alla marcia)",
+                &["Ludwig van Beethoven", "Berliner Philharmoniker", "Herbert von Karajan"],
+                "Beethoven: The Complete Symphonies (Remastered Deluxe Anniversary Edition)",
+                "1963",
+            ),
+            track(2, "Short", &["A"], "B", "2020"),
+        ],
+        vec![artist(
+            1,
+            "2wOqMjp9TyABvtHdOSOTUS",
+            "Herbert von Karajan and the Berliner Philharmoniker with the Wiener Singverein",
+            &["classical", "german romanticism", "orchestral", "early romantic era", "late romantic era"],
+        )],
+    )
+}
+
+fn wide_unicode_model() -> Model {
+    fixture(
+        vec![
+            track(1, "紅蓮華", &["LiSA"], "紅蓮華", "2019"),
+            track(2, "夜に駆ける", &["YOASOBI"], "THE BOOK", "2021"),
+            track(3, "강남스타일", &["PSY"], "싸이6甲", "2012"),
+            track(
+                4,
+                "🔥 Fire Emoji Anthem 🔥",
+                &["DJ 🎧"],
+                "Ｆｕｌｌｗｉｄｔｈ",
+                "2023",
+            ),
+        ],
+        vec![
+            artist(
+                1,
+                "3Nrfpe0tUJi4K4DXYWgMUX",
+                "米津玄師",
+                &["j-pop", "アニソン"],
+            ),
+            artist(2, "64tJ2EAv1R6UaZqc4iOCyj", "YOASOBI", &["j-pop"]),
+            artist(3, "2dd5mrQZvg6SmahdgVKDzh", "방탄소년단", &["k-pop"]),
+        ],
+    )
+}
+
+/// Names full of characters that HTML has to escape.
+fn markup_model() -> Model {
+    fixture(
+        vec![
+            track(
+                1,
+                "<Intro> & \"Outro\"",
+                &["Simon & Garfunkel"],
+                "Bridge <Over> Troubled Water",
+                "1970",
+            ),
+            track(
+                2,
+                "Boogie Wonderland",
+                &["Earth, Wind & Fire"],
+                "I Am",
+                "1979",
+            ),
+        ],
+        vec![
+            artist(
+                1,
+                "70cRZdQywnSFp9pnc2WTCE",
+                "Simon & Garfunkel",
+                &["folk rock", "<classic>"],
+            ),
+            artist(
+                2,
+                "4QQgXkCYTt3BlENzhyNETg",
+                "Earth, Wind & Fire",
+                &["r&b", "\"disco\""],
+            ),
+        ],
+    )
+}
+
+fn playing(name: &str, artists: &[&str]) -> NowPlaying {
+    NowPlaying {
+        track_id: None,
+        name: name.to_string(),
+        artists: artists.iter().map(|a| a.to_string()).collect(),
+        device: "Kitchen Speaker".to_string(),
+        is_playing: true,
+        progress_ms: 83_000,
+        duration_ms: 205_000,
+    }
+}
+
+/// Renders `render` into a `width` x `height` buffer.
+fn render(width: u16, height: u16, render: impl FnOnce(&mut Frame)) -> TestBackend {
+    let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+    terminal.draw(render).unwrap();
+    terminal.backend().clone()
+}
+
+fn render_screen(model: &mut Model, width: u16, height: u16) -> TestBackend {
+    render(width, height, |frame| draw(model, frame))
+}
+
+fn render_pane(model: &mut Model, pane: Pane, width: u16, height: u16) -> TestBackend {
+    render(width, height, |frame| {
+        pane::render(model, frame, pane, Rect::new(0, 0, width, height))
+    })
+}
+
+/// Compares the text of the rendered buffer with the snapshot `name`, or
+/// overwrites the snapshot when `UPDATE_SNAPSHOTS` is set.
+fn assert_snapshot(name: &str, backend: &TestBackend) {
+    assert_golden(&format!("{}.txt", name), &backend.to_string());
+}
+
+/// Compares `actual` with the snapshot file `file`, or overwrites the file
+/// when `UPDATE_SNAPSHOTS` is set.
+fn assert_golden(file: &str, actual: &str) {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("src/app/view/snapshots")
+        .join(file);
+
+    if std::env::var_os("UPDATE_SNAPSHOTS").is_some() {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, actual).unwrap();
+        return;
+    }
+
+    let expected = std::fs::read_to_string(&path).unwrap_or_else(|e| {
+        panic!(
+            "could not read snapshot {}: {}, rerun with UPDATE_SNAPSHOTS=1 to create it",
+            path.display(),
+            e
+        )
+    });
+    assert!(
+        actual == expected,
+        "snapshot {} does not match, rerun with UPDATE_SNAPSHOTS=1 if the change is intended\n\
+         expected:\n{}\nactual:\n{}",
+        file,
+        expected,
+        actual
+    );
+}
+
+#[test]
+fn tracks_pane() {
+    let mut model = sample_model();
+    assert_snapshot("tracks_pane", &render_pane(&mut model, Pane::Tracks, 90, 9));
+}
+
+#[test]
+fn tracks_pane_narrow_hides_columns() {
+    let mut model = sample_model();
+    assert_snapshot(
+        "tracks_pane_narrow",
+        &render_pane(&mut model, Pane::Tracks, 40, 9),
+    );
+}
+
+#[test]
+fn tracks_pane_sorted_by_title() {
+    let mut model = sample_model();
+    model.tracks_state.sort_column = 1;
+    model.tracks_state.sort_descending = false;
+    assert_snapshot(
+        "tracks_pane_sorted",
+        &render_pane(&mut model, Pane::Tracks, 90, 9),
+    );
+}
+
+#[test]
+fn artists_pane() {
+    let mut model = sample_model();
+    model.focus = Pane::Artists;
+    assert_snapshot(
+        "artists_pane",
+        &render_pane(&mut model, Pane::Artists, 70, 8),
+    );
+}
+
+#[test]
+fn empty_panes() {
+    let mut model = fixture(Vec::new(), Vec::new());
+    assert_snapshot(
+        "empty_tracks_pane",
+        &render_pane(&mut model, Pane::Tracks, 60, 5),
+    );
+    assert_snapshot(
+        "empty_artists_pane",
+        &render_pane(&mut model, Pane::Artists, 60, 5),
+    );
+}
+
+#[test]
+fn loading_pane() {
+    let mut model = Model::new();
+    model.loading.push(TimeRange::ShortTerm);
+    assert_snapshot(
+        "loading_pane",
+        &render_pane(&mut model, Pane::Tracks, 60, 5),
+    );
+}
+
+#[test]
+fn long_names_are_truncated() {
+    let mut model = long_names_model();
+    assert_snapshot(
+        "long_names_tracks_pane",
+        &render_pane(&mut model, Pane::Tracks, 90, 6),
+    );
+    assert_snapshot(
+        "long_names_artists_pane",
+        &render_pane(&mut model, Pane::Artists, 70, 5),
+    );
+}
+
+#[test]
+fn wide_unicode_titles() {
+    let mut model = wide_unicode_model();
+    assert_snapshot(
+        "wide_unicode_tracks_pane",
+        &render_pane(&mut model, Pane::Tracks, 90, 8),
+    );
+    assert_snapshot(
+        "wide_unicode_artists_pane",
+        &render_pane(&mut model, Pane::Artists, 61, 7),
+    );
+}
+
+#[test]
+fn empty_screen() {
+    let mut model = Model::new();
+    assert_snapshot("empty_screen", &render_screen(&mut model, 100, 30));
+}
+
+#[test]
+fn normal_screen() {
+    let mut model = sample_model();
+    assert_snapshot("normal_screen", &render_screen(&mut model, 120, 36));
+}
+
+#[test]
+fn narrow_screen() {
+    let mut model = wide_unicode_model();
+    assert_snapshot("narrow_screen", &render_screen(&mut model, 60, 16));
+}
+
+#[test]
+fn wide_screen() {
+    let mut model = sample_model();
+    assert_snapshot("wide_screen", &render_screen(&mut model, 160, 30));
+}
+
+#[test]
+fn report() {
+    let mut model = sample_model();
+    update(&mut model, Message::ToggleReport);
+    assert_snapshot("report", &render_screen(&mut model, 100, 30));
+
+    for _ in 0..10 {
+        update(&mut model, Message::NextPage);
+    }
+    assert_snapshot("report_about", &render_screen(&mut model, 100, 30));
+}
+
+#[test]
+fn help_overlay() {
+    let mut model = sample_model();
+    model.show_help = true;
+    assert_snapshot("help_overlay", &render_screen(&mut model, 100, 30));
+}
+
+#[test]
+fn too_small_screen() {
+    let mut model = sample_model();
+    assert_snapshot("too_small_screen", &render_screen(&mut model, 30, 8));
+}
+
+#[test]
+fn selected_row_is_highlighted() {
+    let mut model = sample_model();
+    // The row count is only known once the pane has been drawn.
+    render_pane(&mut model, Pane::Tracks, 90, 9);
+    model.tracks_state.select(1);
+    let backend = render_pane(&mut model, Pane::Tracks, 90, 9);
+
+    // Border, header, then the rows.
+    let cell = &backend.buffer()[(1, 3)];
+    assert_eq!(cell.symbol(), ">");
+    assert_eq!(Some(cell.fg), model.theme.selected.fg);
+    assert_eq!(Some(cell.bg), model.theme.selected.bg);
+}
+
+#[test]
+fn now_playing_panel() {
+    let mut model = sample_model();
+    model.now_playing = Some(playing("Hyperballad", &["Björk"]));
+    assert_snapshot(
+        "now_playing_panel",
+        &render(80, now_playing::HEIGHT, |frame| {
+            now_playing::render(&model, frame, frame.area())
+        }),
+    );
+    assert_snapshot(
+        "now_playing_line",
+        &render(60, now_playing::NARROW_HEIGHT, |frame| {
+            now_playing::render(&model, frame, frame.area())
+        }),
+    );
+}
+
+#[test]
+fn now_playing_screen() {
+    let mut model = wide_unicode_model();
+    let mut now_playing = playing("夜に駆ける", &["YOASOBI"]);
+    now_playing.is_playing = false;
+    model.now_playing = Some(now_playing);
+    assert_snapshot("now_playing_screen", &render_screen(&mut model, 100, 30));
+}
+
+#[test]
+fn filtered_pane_highlights_matches() {
+    let mut model = sample_model();
+    model.search = "rad".into();
+    let backend = render_pane(&mut model, Pane::Tracks, 90, 9);
+    assert_snapshot("filtered_tracks_pane", &backend);
+
+    // The r of Hyperballad, in the second row left by the filter.
+    let cell = &backend.buffer()[(12, 3)];
+    assert_eq!(cell.symbol(), "r");
+    assert_eq!(Some(cell.fg), model.theme.matched.fg);
+    let cell = &backend.buffer()[(11, 3)];
+    assert_eq!(cell.symbol(), "e");
+    assert_ne!(Some(cell.fg), model.theme.matched.fg);
+}
+
+#[test]
+fn playing_track_is_highlighted() {
+    let mut model = sample_model();
+    model.now_playing = Some(playing("Windowlicker", &["Aphex Twin"]));
+    let backend = render_pane(&mut model, Pane::Tracks, 90, 9);
+
+    // The third track, below the border and the header.
+    let cell = &backend.buffer()[(8, 4)];
+    assert_eq!(cell.symbol(), "W");
+    assert_eq!(Some(cell.fg), model.theme.playing.fg);
+    assert!(cell.modifier.contains(Modifier::BOLD));
+}
+
+#[test]
+fn device_picker() {
+    let mut model = sample_model();
+    model.devices = Some(DevicePicker::default());
+    assert_snapshot("device_picker_loading", &render_screen(&mut model, 100, 30));
+
+    let device = |name: &str, kind: &str, is_active: bool| PlaybackDevice {
+        id: name.to_lowercase(),
+        name: name.to_string(),
+        kind: kind.to_string(),
+        is_active,
+    };
+    model.devices = Some(DevicePicker {
+        devices: Some(vec![
+            device("Laptop", "Computer", true),
+            device("Kitchen", "Speaker", false),
+        ]),
+        selected: 1,
+    });
+    assert_snapshot("device_picker", &render_screen(&mut model, 100, 30));
+}
+
+#[test]
+fn playlist_preview() {
+    let mut model = sample_model();
+    let planned = |id: &str, name: &str, artist: &str| PlannedTrack {
+        id: TrackId::from_id(id.to_string()).unwrap(),
+        name: name.to_string(),
+        artists: vec![artist.to_string()],
+    };
+    model.playlist_plan = Some(PlaylistPlan {
+        user_id: UserId::from_id("listener".to_string()).unwrap(),
+        name: "Top 2 – Short Term – 2026-05-31".to_string(),
+        existing: None,
+        public: false,
+        mode: WriteMode::Replace,
+        time_range: TimeRange::ShortTerm,
+        tracks: vec![
+            planned("4uLU6hMCjMI75M1A2tKUQC", "Teardrop", "Massive Attack"),
+            planned("7ouMYWpwJ422jRcDASZB7P", "Windowlicker", "Aphex Twin"),
+        ],
+        skipped: 0,
+    });
+
+    assert_snapshot("playlist_preview", &render_screen(&mut model, 100, 30));
+}
+
+fn seed_names(seeds: &[Seed]) -> Vec<&str> {
+    seeds.iter().map(|seed| seed.name()).collect()
+}
+
+#[test]
+fn discover() {
+    let mut model = sample_model();
+    model.discover_options.targets.energy = Some(0.8);
+
+    let Some(Command::FetchRecommendations(seeds)) = update(&mut model, Message::ToggleDiscover)
+    else {
+        panic!("discovery should fetch recommendations");
+    };
+    assert_eq!(seed_names(&seeds), ["Radiohead", "Björk", "Aphex Twin"]);
+    assert_snapshot("discover_loading", &render_screen(&mut model, 100, 30));
+
+    let recommendation = |id: &str, name: &str, artist: &str, seeds: &[&str]| Recommendation {
+        id: TrackId::from_id(id.to_string()).unwrap(),
+        name: name.to_string(),
+        artists: vec![artist.to_string()],
+        seeds: seeds.iter().map(|seed| seed.to_string()).collect(),
+    };
+    let recommendations = vec![
+        recommendation("1", "Glass Eyes", "Radiohead", &["Radiohead"]),
+        recommendation("2", "Army of Me", "Björk", &["Björk", "Aphex Twin"]),
+        recommendation("3", "Xtal", "Aphex Twin", &["Aphex Twin"]),
+    ];
+    update(
+        &mut model,
+        Message::RecommendationsLoaded(Ok(recommendations)),
+    );
+    update(&mut model, Message::ScrollDown);
+    assert_snapshot("discover", &render_screen(&mut model, 100, 30));
+    assert_eq!(model.selected_track_id(), TrackId::from_id("2").ok());
+}
+
+#[test]
+fn picked_seeds_replace_automatic_ones() {
+    let mut model = sample_model();
+    render_screen(&mut model, 100, 30);
+    model.focus = Pane::Artists;
+    update(&mut model, Message::ScrollDown);
+    update(&mut model, Message::ToggleSeed);
+    update(&mut model, Message::ScrollDown);
+    update(&mut model, Message::ToggleSeed);
+    update(&mut model, Message::ToggleSeed);
+
+    let command = update(&mut model, Message::ToggleDiscover);
+    let Some(Command::FetchRecommendations(seeds)) = command else {
+        panic!("discovery should fetch recommendations");
+    };
+    assert_eq!(seed_names(&seeds), ["Björk"]);
+    assert_eq!(model.toasts.len(), 3);
+}
+
+#[test]
+fn artist_graph() {
+    let mut model = sample_model();
+
+    let Some(Command::BuildGraph(top, listened)) = update(&mut model, Message::ToggleGraph) else {
+        panic!("the artist graph should be built");
+    };
+    assert_snapshot("graph_loading", &render_screen(&mut model, 100, 30));
+
+    let related = |id: &str, name: &str, genre: &str| GraphArtist {
+        id: id.to_string(),
+        name: name.to_string(),
+        genres: vec![genre.to_string()],
+        popularity: 60,
+    };
+    let mut graph = ArtistGraph::new(top, listened);
+    let radiohead = graph.nodes[0].artist.id.clone();
+    let aphex_twin = graph.nodes[2].artist.id.clone();
+    graph.expand(
+        &radiohead,
+        vec![
+            related("thom", "Thom Yorke", "art rock"),
+            related("portishead", "Portishead", "trip hop"),
+        ],
+        100,
+    );
+    graph.expand(
+        &aphex_twin,
+        vec![
+            related("boards", "Boards of Canada", "idm"),
+            related("autechre", "Autechre", "idm"),
+            related("portishead", "Portishead", "trip hop"),
+        ],
+        100,
+    );
+    update(&mut model, Message::GraphLoaded(Ok(Box::new(graph))));
+    assert_snapshot("graph", &render_screen(&mut model, 100, 30));
+
+    assert_eq!(
+        update(&mut model, Message::ExportReport),
+        Some(Command::Export(Export::Graph))
+    );
+}
+
+#[test]
+fn new_releases() {
+    let mut model = sample_model();
+    let ok_computer = AlbumId::from_id("6dVIqQ8qmQ5GBnJ9shOYGE".to_string()).unwrap();
+    model.top_tracks[0].tracks[0].album_id = Some(ok_computer.clone());
+
+    let Some(Command::FetchReleases(artists, played)) = update(&mut model, Message::ToggleReleases)
+    else {
+        panic!("the new releases should be fetched");
+    };
+    assert_eq!(artists.len(), 4);
+    assert_eq!(played, HashSet::from([ok_computer]));
+    assert_snapshot("releases_loading", &render_screen(&mut model, 100, 30));
+
+    let release = |id: &str, name: &str, kind: &str, released: &str, new: bool| Release {
+        id: AlbumId::from_id(id.to_string()).unwrap(),
+        name: name.to_string(),
+        artists: vec!["Björk".to_string()],
+        kind: kind.to_string(),
+        release_date: parse_release_date(released).unwrap(),
+        new,
+    };
+    let releases = vec![
+        release("1", "Atopos", "single", "2026-10-12", true),
+        release("2", "Fossora", "album", "2026-10-01", false),
+    ];
+    update(&mut model, Message::ReleasesLoaded(Ok(releases)));
+    update(&mut model, Message::ScrollDown);
+    assert_snapshot("releases", &render_screen(&mut model, 100, 30));
+}
+
+#[test]
+fn html_stats_page() {
+    let model = markup_model();
+    let page = html::render_stats(&model, "2026-06-01 12:00");
+    assert!(!page.contains("<Intro>") && !page.contains("<classic>"));
+    assert_golden("stats.html", &page);
+}
+
+#[test]
+fn html_report() {
+    let mut model = markup_model();
+    model.toggle_report();
+    let report = model.report.as_ref().unwrap();
+    assert_golden("report.html", &html::render_report(report));
+}
+
+#[test]
+fn html_genre_chart() {
+    let genres = vec![
+        ("r&b".to_string(), 3),
+        ("<classic>".to_string(), 2),
+        ("\"disco\"".to_string(), 1),
+    ];
+    assert_golden("genre_chart.svg", &html::genre_chart_svg(&genres));
+}
+
+#[test]
+fn html_decade_chart() {
+    let model = sample_model();
+    assert_golden(
+        "decade_chart.svg",
+        &html::decade_chart_svg(&model.release_eras[0].decades),
+    );
+}
@@ -0,0 +1,162 @@
+use std::path::PathBuf;
+
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use rspotify::model::TimeRange;
+
+use crate::playlist::{PlaylistOptions, WriteMode};
+
+/// Your Spotify top tracks and artists in the terminal.
+///
+/// Starts the TUI when no subcommand is given.
+#[derive(Debug, Parser)]
+#[command(version, about)]
+pub struct Cli {
+    /// Config file to use instead of the default one.
+    #[arg(long, global = true)]
+    pub config: Option<PathBuf>,
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Export a shareable stats card as SVG (and optionally PNG).
+    Card {
+        #[arg(short, long, value_enum, default_value_t = Range::Short)]
+        range: Range,
+        /// Also rasterize the card into a PNG (requires the `png` feature).
+        #[arg(long)]
+        png: bool,
+        /// Directory to write the card into.
+        #[arg(short, long, default_value = ".")]
+        out: PathBuf,
+    },
+    /// Write the top tracks of a range into a playlist.
+    ///
+    /// The playlist named after --name is used, and created when the user has
+    /// none by that name.
+    Playlist(PlaylistArgs),
+    /// Keep rolling playlists of the short term top tracks up to date.
+    ///
+    /// By default that is a playlist per month and a "Current Top 30", set
+    /// up with [[rolling]] tables in the config. Only playlists made by this
+    /// command, as recorded in its state file, are ever changed.
+    Rolling {
+        /// Keep running, syncing again every this many hours.
+        #[arg(long, value_parser = clap::value_parser!(u64).range(1..))]
+        every: Option<u64>,
+        /// State file to use instead of the default one.
+        #[arg(long)]
+        state: Option<PathBuf>,
+        /// Print what would be done without changing anything.
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Map the top artists and their related artists as a graph.
+    ///
+    /// Prints its clusters, the artists bridging them and the related
+    /// artists not listened to yet, and writes it as DOT and GraphML.
+    Graph {
+        /// How many steps of related artists to follow. Defaults to the one
+        /// in the config.
+        #[arg(short, long, value_parser = clap::value_parser!(u64).range(1..=3))]
+        depth: Option<u64>,
+        /// How many top artists of each range to start from.
+        #[arg(short, long, default_value_t = 10, value_parser = clap::value_parser!(u8).range(1..=50))]
+        limit: u8,
+        /// Fetch related artists again instead of using the cached ones.
+        #[arg(long)]
+        refresh: bool,
+        /// Directory to write the graph into.
+        #[arg(short, long, default_value = ".")]
+        out: PathBuf,
+    },
+    /// List recent albums and singles of the top artists not saved or
+    /// played yet.
+    ///
+    /// Releases not listed by an earlier run are starred.
+    Releases {
+        /// How many days back to look. Defaults to the one in the config.
+        #[arg(short, long)]
+        days: Option<u32>,
+        /// How many top artists of each range to check.
+        #[arg(short, long, default_value_t = 20, value_parser = clap::value_parser!(u8).range(1..=50))]
+        limit: u8,
+        /// State file to use instead of the default one.
+        #[arg(long)]
+        state: Option<PathBuf>,
+    },
+}
+
+#[derive(Debug, Args)]
+pub struct PlaylistArgs {
+    #[arg(short, long, value_enum, default_value_t = Range::Short)]
+    pub range: Range,
+    /// How many top tracks to write.
+    #[arg(short, long, default_value_t = 50, value_parser = clap::value_parser!(u8).range(1..=50))]
+    pub limit: u8,
+    /// Name template, filling in {range}, {count}, {date}, {month} and
+    /// {year}. Defaults to the one in the config.
+    #[arg(short, long)]
+    pub name: Option<String>,
+    /// Write into this playlist, given as an id, URI or link, instead.
+    #[arg(long)]
+    pub playlist: Option<String>,
+    /// Make a created playlist public.
+    #[arg(long, conflicts_with = "private")]
+    pub public: bool,
+    /// Make a created playlist private.
+    #[arg(long)]
+    pub private: bool,
+    /// What to do with the tracks already in the playlist.
+    #[arg(short, long, value_enum)]
+    pub mode: Option<Mode>,
+    /// Print what would be written without changing anything.
+    #[arg(long)]
+    pub dry_run: bool,
+}
+
+impl PlaylistArgs {
+    /// The config's options with the ones given on the command line on top.
+    pub fn options(&self, config: &PlaylistOptions) -> PlaylistOptions {
+        PlaylistOptions {
+            name: self.name.clone().unwrap_or_else(|| config.name.clone()),
+            public: (config.public || self.public) && !self.private,
+            mode: self.mode.map(WriteMode::from).unwrap_or(config.mode),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Range {
+    Short,
+    Medium,
+    Long,
+}
+
+impl From<Range> for TimeRange {
+    fn from(range: Range) -> Self {
+        match range {
+            Range::Short => TimeRange::ShortTerm,
+            Range::Medium => TimeRange::MediumTerm,
+            Range::Long => TimeRange::LongTerm,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Mode {
+    /// Swap them for the top tracks.
+    Replace,
+    /// Keep them and add the top tracks after them.
+    Append,
+}
+
+impl From<Mode> for WriteMode {
+    fn from(mode: Mode) -> Self {
+        match mode {
+            Mode::Replace => WriteMode::Replace,
+            Mode::Append => WriteMode::Append,
+        }
+    }
+}
@@ -1,7 +1,9 @@
 use chrono::Duration;
 use futures::{pin_mut, TryStreamExt};
-use rspotify::clients::OAuthClient;
-use rspotify::model::{SimplifiedArtist, TimeRange};
+use rspotify::clients::{BaseClient, OAuthClient};
+use rspotify::model::{
+    AdditionalType, AlbumId, ArtistId, PlayableItem, SimplifiedArtist, TimeRange, TrackId,
+};
 use rspotify::{scopes, ClientError};
 use rspotify::{AuthCodeSpotify, Credentials, OAuth};
 
@@ -9,7 +11,7 @@ pub fn get_env_var(key: &str) -> String {
     std::env::var(key).unwrap_or_else(|_| panic!("Variable not found: {}", key))
 }
 
-fn format_duration(duration: Duration) -> String {
+pub fn format_duration(duration: Duration) -> String {
     let total_seconds = duration.num_seconds();
     let minutes = total_seconds / 60;
     let seconds = total_seconds % 60;
@@ -17,6 +19,29 @@ fn format_duration(duration: Duration) -> String {
     format!("{}:{:02}", minutes, seconds)
 }
 
+pub const TIME_RANGES: [TimeRange; 3] = [
+    TimeRange::ShortTerm,
+    TimeRange::MediumTerm,
+    TimeRange::LongTerm,
+];
+
+pub fn show_time_range(time_range: &TimeRange) -> String {
+    match time_range {
+        TimeRange::ShortTerm => "Short Term".to_string(),
+        TimeRange::MediumTerm => "Medium Term".to_string(),
+        TimeRange::LongTerm => "Long Term".to_string(),
+    }
+}
+
+/// A file name friendly version of [`show_time_range`].
+pub fn time_range_slug(time_range: &TimeRange) -> &'static str {
+    match time_range {
+        TimeRange::ShortTerm => "short-term",
+        TimeRange::MediumTerm => "medium-term",
+        TimeRange::LongTerm => "long-term",
+    }
+}
+
 pub struct Client {
     pub creds: Credentials,
     pub redirect_uri: String,
@@ -24,7 +49,17 @@ pub struct Client {
 
 impl Client {
     pub async fn auth(&self) -> Option<AuthCodeSpotify> {
-        let oauth = match OAuth::from_env(scopes!("user-top-read")) {
+        let oauth = match OAuth::from_env(scopes!(
+            "user-top-read",
+            "user-read-currently-playing",
+            "user-read-playback-state",
+            "user-modify-playback-state",
+            "playlist-read-private",
+            "playlist-modify-public",
+            "playlist-modify-private",
+            "user-library-read",
+            "user-read-recently-played"
+        )) {
             Some(oauth) => oauth,
             None => {
                 println!("Failed to retrieve OAuth from environment.");
@@ -46,17 +81,15 @@ impl Client {
     }
 }
 
-pub async fn get_user_display_name(client: &AuthCodeSpotify) -> String {
-    match client.me().await {
-        Ok(user) => match user.display_name {
-            Some(username) => username,
-            None => "Unknown User".to_string(),
-        },
-        Err(e) => {
-            println!("Error fetching user: {:?}", e);
-            "Unknown User".to_string()
-        }
-    }
+pub async fn get_user_display_name(client: &AuthCodeSpotify) -> Result<String, ClientError> {
+    let user = client.me().await?;
+
+    let result = match user.display_name {
+        Some(username) => username,
+        None => "Unknown User".to_string(),
+    };
+
+    Ok(result)
 }
 
 pub fn get_artists(artists: Vec<SimplifiedArtist>) -> Vec<String> {
@@ -64,15 +97,23 @@ pub fn get_artists(artists: Vec<SimplifiedArtist>) -> Vec<String> {
     artists
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct TopTrack {
     pub index: usize,
+    pub id: Option<TrackId<'static>>,
     pub track_name: String,
     pub duration: String,
+    pub duration_ms: i64,
     pub artists: Vec<String>,
+    /// `None` for local files.
+    pub album_id: Option<AlbumId<'static>>,
+    pub album_name: String,
+    pub album_artists: Vec<String>,
+    pub release_date: Option<String>,
+    pub popularity: u32,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct TopTracks {
     pub time_range: TimeRange,
     pub tracks: Vec<TopTrack>,
@@ -91,9 +132,16 @@ pub async fn get_top_tracks(
     while let Some(item) = stream.try_next().await? {
         let top_track = TopTrack {
             index: tracks.len() + 1,
+            id: item.id.clone(),
             track_name: item.name.clone(),
             duration: format_duration(item.duration),
+            duration_ms: item.duration.num_milliseconds(),
             artists: get_artists(item.artists.clone()),
+            album_id: item.album.id.clone(),
+            album_name: item.album.name.clone(),
+            album_artists: get_artists(item.album.artists.clone()),
+            release_date: item.album.release_date.clone(),
+            popularity: item.popularity,
         };
 
         if top_track.index as u8 == limit + 1 {
@@ -107,45 +155,150 @@ pub async fn get_top_tracks(
     Ok(result)
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct TopArtist {
     pub index: usize,
+    pub id: ArtistId<'static>,
     pub artist_name: String,
-    pub genres: String,
+    pub genres: Vec<String>,
+    pub popularity: u32,
+    pub followers: u32,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct TopArtists {
     pub time_range: TimeRange,
     pub artists: Vec<TopArtist>,
 }
+
 pub async fn get_top_artists(
     client: &AuthCodeSpotify,
     time_range: TimeRange,
     limit: u8,
-) -> Result<Vec<TopArtist>, ClientError> {
+) -> Result<TopArtists, ClientError> {
     let stream = client.current_user_top_artists(Some(time_range));
     pin_mut!(stream);
 
-    let mut result: Vec<TopArtist> = Vec::new();
+    let mut artists: Vec<TopArtist> = Vec::new();
 
     while let Some(item) = stream.try_next().await? {
         let top_artist = TopArtist {
-            index: result.len() + 1,
+            index: artists.len() + 1,
+            id: item.id.clone(),
             artist_name: item.name.clone(),
-            genres: item
-                .genres
-                .iter()
-                .take(3)
-                .cloned()
-                .collect::<Vec<String>>()
-                .join(", "),
+            genres: item.genres.clone(),
+            popularity: item.popularity,
+            followers: item.followers.total,
         };
 
         if top_artist.index as u8 == limit + 1 {
             break;
         }
-        result.push(top_artist);
+        artists.push(top_artist);
     }
+
+    let result = TopArtists {
+        time_range,
+        artists,
+    };
+
     Ok(result)
 }
+
+/// The audio features of a track that are worth showing to a listener.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TrackDetails {
+    pub danceability: f32,
+    pub energy: f32,
+    pub valence: f32,
+    pub acousticness: f32,
+    pub instrumentalness: f32,
+    pub tempo: f32,
+}
+
+pub async fn get_track_details(
+    client: &AuthCodeSpotify,
+    id: TrackId<'static>,
+) -> Result<TrackDetails, ClientError> {
+    let features = client.track_features(id).await?;
+
+    let result = TrackDetails {
+        danceability: features.danceability,
+        energy: features.energy,
+        valence: features.valence,
+        acousticness: features.acousticness,
+        instrumentalness: features.instrumentalness,
+        tempo: features.tempo,
+    };
+
+    Ok(result)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ArtistDetails {
+    pub related_artists: Vec<String>,
+}
+
+pub async fn get_artist_details(
+    client: &AuthCodeSpotify,
+    id: ArtistId<'static>,
+) -> Result<ArtistDetails, ClientError> {
+    let related = client.artist_related_artists(id).await?;
+
+    let result = ArtistDetails {
+        related_artists: related.into_iter().map(|artist| artist.name).collect(),
+    };
+
+    Ok(result)
+}
+
+/// What is playing on the user's active device.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NowPlaying {
+    /// `None` for episodes and local files.
+    pub track_id: Option<TrackId<'static>>,
+    pub name: String,
+    /// The artists of a track, or the show of an episode.
+    pub artists: Vec<String>,
+    pub device: String,
+    pub is_playing: bool,
+    pub progress_ms: i64,
+    pub duration_ms: i64,
+}
+
+/// Returns `None` when nothing is playing, or when the player is idle.
+pub async fn get_now_playing(client: &AuthCodeSpotify) -> Result<Option<NowPlaying>, ClientError> {
+    let types = [AdditionalType::Track, AdditionalType::Episode];
+    let playback = match client.current_playback(None, Some(&types)).await? {
+        Some(playback) => playback,
+        None => return Ok(None),
+    };
+
+    let (track_id, name, artists, duration) = match playback.item {
+        Some(PlayableItem::Track(track)) => (
+            track.id,
+            track.name,
+            get_artists(track.artists),
+            track.duration,
+        ),
+        Some(PlayableItem::Episode(episode)) => (
+            None,
+            episode.name,
+            vec![episode.show.name],
+            episode.duration,
+        ),
+        None => return Ok(None),
+    };
+
+    let result = NowPlaying {
+        track_id,
+        name,
+        artists,
+        device: playback.device.name,
+        is_playing: playback.is_playing,
+        progress_ms: playback.progress.map_or(0, |p| p.num_milliseconds()),
+        duration_ms: duration.num_milliseconds(),
+    };
+
+    Ok(Some(result))
+}
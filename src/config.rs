@@ -0,0 +1,186 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+
+use crate::discover::{DiscoverOptions, TargetError};
+use crate::graph::{DepthError, GraphOptions};
+use crate::keymap::{Action, Keymap, KeymapError, Preset};
+use crate::playlist::{PlaylistOptions, TemplateError};
+use crate::releases::ReleaseOptions;
+use crate::rolling::RollingPlaylist;
+use crate::theme::{no_color, Theme, ThemeColors, ThemeError};
+
+/// Settings read from `config.toml`, e.g.
+///
+/// ```toml
+/// theme = "light"
+///
+/// [keys]
+/// preset = "arrows"
+///
+/// [keys.bindings]
+/// quit = ["q", "ctrl-c"]
+///
+/// [playlist]
+/// name = "Top {count} – {range} – {date}"
+/// public = false
+/// mode = "replace"
+///
+/// [[rolling]]
+/// rotation = "monthly"
+/// keep = 12
+///
+/// [discover.targets]
+/// energy = 0.8
+///
+/// [graph]
+/// depth = 2
+///
+/// [releases]
+/// days = 14
+/// ```
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// Name of a built-in theme or of one of `themes`.
+    pub theme: Option<String>,
+    pub themes: HashMap<String, ThemeColors>,
+    pub keys: KeysConfig,
+    pub playlist: PlaylistOptions,
+    /// The playlists kept up to date by the `rolling` subcommand.
+    pub rolling: Vec<RollingPlaylist>,
+    pub discover: DiscoverOptions,
+    pub graph: GraphOptions,
+    pub releases: ReleaseOptions,
+}
+
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct KeysConfig {
+    pub preset: Preset,
+    /// Replaces the preset keys of each listed action.
+    pub bindings: HashMap<Action, Vec<String>>,
+}
+
+#[derive(Debug)]
+pub enum ConfigError {
+    Read(PathBuf, std::io::Error),
+    Parse(PathBuf, toml::de::Error),
+    Keymap(PathBuf, KeymapError),
+    Theme(PathBuf, ThemeError),
+    Playlist(PathBuf, TemplateError),
+    Discover(PathBuf, TargetError),
+    Graph(PathBuf, DepthError),
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigError::Read(path, e) => write!(f, "could not read {}: {}", path.display(), e),
+            ConfigError::Parse(path, e) => write!(f, "invalid config {}: {}", path.display(), e),
+            ConfigError::Keymap(path, e) => write!(f, "invalid keys in {}: {}", path.display(), e),
+            ConfigError::Theme(path, e) => write!(f, "invalid theme in {}: {}", path.display(), e),
+            ConfigError::Playlist(path, e) => {
+                write!(f, "invalid playlist name in {}: {}", path.display(), e)
+            }
+            ConfigError::Discover(path, e) => {
+                write!(f, "invalid discover target in {}: {}", path.display(), e)
+            }
+            ConfigError::Graph(path, e) => {
+                write!(f, "invalid graph depth in {}: {}", path.display(), e)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+/// `$XDG_CONFIG_HOME/spotifystats/config.toml` or the platform equivalent.
+pub fn default_path() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join("spotifystats").join("config.toml"))
+}
+
+impl Config {
+    /// Reads the config at `path`, or at [`default_path`] when it is `None`.
+    ///
+    /// A missing default config is not an error, but a missing explicit one is.
+    pub fn load(path: Option<&Path>) -> Result<Config, ConfigError> {
+        let (path, required) = match path {
+            Some(path) => (path.to_path_buf(), true),
+            None => match default_path() {
+                Some(path) => (path, false),
+                None => return Ok(Config::default()),
+            },
+        };
+
+        let text = match std::fs::read_to_string(&path) {
+            Ok(text) => text,
+            Err(e) if !required && e.kind() == std::io::ErrorKind::NotFound => {
+                return Ok(Config::default())
+            }
+            Err(e) => return Err(ConfigError::Read(path, e)),
+        };
+
+        let config: Config =
+            toml::from_str(&text).map_err(|e| ConfigError::Parse(path.clone(), e))?;
+        config
+            .keymap()
+            .map_err(|e| ConfigError::Keymap(path.clone(), e))?;
+        config
+            .theme()
+            .map_err(|e| ConfigError::Theme(path.clone(), e))?;
+        config
+            .playlist
+            .validate()
+            .map_err(|e| ConfigError::Playlist(path.clone(), e))?;
+        config
+            .discover
+            .targets
+            .validate()
+            .map_err(|e| ConfigError::Discover(path.clone(), e))?;
+        config
+            .graph
+            .validate()
+            .map_err(|e| ConfigError::Graph(path.clone(), e))?;
+        PlaylistOptions {
+            name: config.discover.playlist_name.clone(),
+            ..Default::default()
+        }
+        .validate()
+        .map_err(|e| ConfigError::Playlist(path.clone(), e))?;
+        for rolling in &config.rolling {
+            rolling
+                .validate()
+                .map_err(|e| ConfigError::Playlist(path.clone(), e))?;
+        }
+
+        Ok(config)
+    }
+
+    pub fn keymap(&self) -> Result<Keymap, KeymapError> {
+        Keymap::load(self.keys.preset, &self.keys.bindings)
+    }
+
+    /// The selected theme, or the monochrome one when `NO_COLOR` is set.
+    pub fn theme(&self) -> Result<Theme, ThemeError> {
+        let name = self.theme.as_deref().unwrap_or("dark");
+        let theme = match self.themes.get(name) {
+            Some(colors) => {
+                let base = colors.base.as_deref().unwrap_or("dark");
+                Theme::builtin(base)
+                    .ok_or_else(|| ThemeError::UnknownTheme(base.to_string()))?
+                    .with_colors(colors)?
+            }
+            None => {
+                Theme::builtin(name).ok_or_else(|| ThemeError::UnknownTheme(name.to_string()))?
+            }
+        };
+
+        if no_color() {
+            return Ok(Theme::monochrome());
+        }
+        Ok(theme)
+    }
+}
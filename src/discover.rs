@@ -0,0 +1,331 @@
+use std::collections::HashSet;
+use std::fmt;
+
+use rspotify::clients::{BaseClient, OAuthClient};
+use rspotify::model::{ArtistId, RecommendationsAttribute, TrackId};
+use rspotify::{AuthCodeSpotify, ClientError};
+use serde::Deserialize;
+
+use crate::client::{get_artists, TopArtists, TopTracks};
+
+/// Spotify takes at most five seeds per request.
+pub const MAX_SEEDS: usize = 5;
+
+/// A top artist or track that recommendations are based on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Seed {
+    Artist { id: ArtistId<'static>, name: String },
+    Track { id: TrackId<'static>, name: String },
+}
+
+impl Seed {
+    pub fn name(&self) -> &str {
+        match self {
+            Seed::Artist { name, .. } | Seed::Track { name, .. } => name,
+        }
+    }
+}
+
+/// Seeds for when none were picked: the top three artists and the top two
+/// tracks that aren't by them.
+pub fn auto_seeds(tracks: &TopTracks, artists: &TopArtists) -> Vec<Seed> {
+    let mut seeds: Vec<Seed> = artists
+        .artists
+        .iter()
+        .take(3)
+        .map(|artist| Seed::Artist {
+            id: artist.id.clone(),
+            name: artist.artist_name.clone(),
+        })
+        .collect();
+    let seed_artists: Vec<String> = seeds.iter().map(|s| s.name().to_string()).collect();
+
+    let seed_tracks = tracks
+        .tracks
+        .iter()
+        .filter(|track| !track.artists.iter().any(|a| seed_artists.contains(a)))
+        .filter_map(|track| {
+            Some(Seed::Track {
+                id: track.id.clone()?,
+                name: track.track_name.clone(),
+            })
+        });
+    seeds.extend(seed_tracks.take(MAX_SEEDS - seeds.len()));
+
+    seeds
+}
+
+/// Audio features the recommendations should be close to, each between 0
+/// and 1 except for the tempo, in BPM.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Targets {
+    pub danceability: Option<f32>,
+    pub energy: Option<f32>,
+    pub valence: Option<f32>,
+    pub acousticness: Option<f32>,
+    pub instrumentalness: Option<f32>,
+    pub tempo: Option<f32>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TargetError {
+    pub feature: &'static str,
+    pub value: f32,
+}
+
+impl fmt::Display for TargetError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.feature {
+            "tempo" => write!(f, "tempo must be positive, got {}", self.value),
+            feature => write!(f, "{} must be between 0 and 1, got {}", feature, self.value),
+        }
+    }
+}
+
+impl std::error::Error for TargetError {}
+
+impl Targets {
+    /// The features that are set, by name.
+    pub fn features(&self) -> Vec<(&'static str, f32)> {
+        [
+            ("danceability", self.danceability),
+            ("energy", self.energy),
+            ("valence", self.valence),
+            ("acousticness", self.acousticness),
+            ("instrumentalness", self.instrumentalness),
+            ("tempo", self.tempo),
+        ]
+        .into_iter()
+        .filter_map(|(feature, value)| Some((feature, value?)))
+        .collect()
+    }
+
+    pub fn validate(&self) -> Result<(), TargetError> {
+        for (feature, value) in self.features() {
+            let valid = match feature {
+                "tempo" => value > 0.0,
+                _ => (0.0..=1.0).contains(&value),
+            };
+            if !valid {
+                return Err(TargetError { feature, value });
+            }
+        }
+        Ok(())
+    }
+
+    fn attributes(&self) -> Vec<RecommendationsAttribute> {
+        let targets = [
+            self.danceability
+                .map(RecommendationsAttribute::TargetDanceability),
+            self.energy.map(RecommendationsAttribute::TargetEnergy),
+            self.valence.map(RecommendationsAttribute::TargetValence),
+            self.acousticness
+                .map(RecommendationsAttribute::TargetAcousticness),
+            self.instrumentalness
+                .map(RecommendationsAttribute::TargetInstrumentalness),
+            self.tempo.map(RecommendationsAttribute::TargetTempo),
+        ];
+        targets.into_iter().flatten().collect()
+    }
+}
+
+/// Settings of the discovery view, read from the `[discover]` section of the
+/// config.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DiscoverOptions {
+    /// How many recommendations to show.
+    pub limit: usize,
+    /// Name template of the playlist the recommendations are saved as.
+    pub playlist_name: String,
+    pub targets: Targets,
+}
+
+impl Default for DiscoverOptions {
+    fn default() -> Self {
+        DiscoverOptions {
+            limit: 30,
+            playlist_name: "Discoveries – {date}".to_string(),
+            targets: Targets::default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Recommendation {
+    pub id: TrackId<'static>,
+    pub name: String,
+    pub artists: Vec<String>,
+    /// The names of the seeds it was recommended for.
+    pub seeds: Vec<String>,
+}
+
+/// Interleaves the recommendations of each seed so that every seed gets its
+/// share of the first `limit`, leaving out those in `known`. A track
+/// recommended for several seeds is listed once, with all of them.
+pub fn merge(
+    batches: Vec<Vec<Recommendation>>,
+    known: &HashSet<TrackId<'static>>,
+    limit: usize,
+) -> Vec<Recommendation> {
+    let mut merged: Vec<Recommendation> = Vec::new();
+    let mut batches: Vec<_> = batches.into_iter().map(|b| b.into_iter()).collect();
+
+    loop {
+        let mut any = false;
+        for batch in &mut batches {
+            let Some(recommendation) = batch.find(|r| !known.contains(&r.id)) else {
+                continue;
+            };
+            any = true;
+            match merged.iter_mut().find(|r| r.id == recommendation.id) {
+                Some(existing) => existing.seeds.extend(recommendation.seeds),
+                None => merged.push(recommendation),
+            }
+        }
+        if !any {
+            break;
+        }
+    }
+
+    merged.truncate(limit);
+    merged
+}
+
+/// Asks for recommendations for each seed on its own, which is what lets
+/// every track say which seed it came from, and leaves out tracks in `known`
+/// or in the user's library.
+pub async fn get_recommendations(
+    client: &AuthCodeSpotify,
+    seeds: &[Seed],
+    options: &DiscoverOptions,
+    known: &HashSet<TrackId<'static>>,
+) -> Result<Vec<Recommendation>, ClientError> {
+    if seeds.is_empty() {
+        return Ok(Vec::new());
+    }
+    // Extra tracks make up for the ones filtered out later.
+    let per_seed = (options.limit / seeds.len() * 2).clamp(10, 100) as u32;
+
+    let mut batches = Vec::new();
+    for seed in seeds {
+        let (artists, tracks) = match seed {
+            Seed::Artist { id, .. } => (Some(vec![id.clone()]), None),
+            Seed::Track { id, .. } => (None, Some(vec![id.clone()])),
+        };
+        let result = client
+            .recommendations(
+                options.targets.attributes(),
+                artists,
+                None::<Vec<&str>>,
+                tracks,
+                None,
+                Some(per_seed),
+            )
+            .await?;
+
+        let batch = result
+            .tracks
+            .into_iter()
+            .filter_map(|track| {
+                Some(Recommendation {
+                    id: track.id?,
+                    name: track.name,
+                    artists: get_artists(track.artists),
+                    seeds: vec![seed.name().to_string()],
+                })
+            })
+            .collect();
+        batches.push(batch);
+    }
+
+    let mut seen = HashSet::new();
+    let candidates: Vec<TrackId<'static>> = batches
+        .iter()
+        .flatten()
+        .map(|r: &Recommendation| r.id.clone())
+        .filter(|id| !known.contains(id) && seen.insert(id.clone()))
+        .collect();
+
+    let mut known = known.clone();
+    // The library can only be checked 50 tracks at a time.
+    for chunk in candidates.chunks(50) {
+        let saved = client
+            .current_user_saved_tracks_contains(chunk.to_vec())
+            .await?;
+        known.extend(
+            chunk
+                .iter()
+                .zip(saved)
+                .filter(|(_, saved)| *saved)
+                .map(|(id, _)| id.clone()),
+        );
+    }
+
+    Ok(merge(batches, &known, options.limit))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn recommendation(id: &str, seed: &str) -> Recommendation {
+        Recommendation {
+            id: TrackId::from_id(id.to_string()).unwrap(),
+            name: id.to_string(),
+            artists: Vec::new(),
+            seeds: vec![seed.to_string()],
+        }
+    }
+
+    fn ids(recommendations: &[Recommendation]) -> Vec<&str> {
+        recommendations.iter().map(|r| r.name.as_str()).collect()
+    }
+
+    #[test]
+    fn merging_takes_turns_between_seeds() {
+        let batches = vec![
+            vec![recommendation("a1", "A"), recommendation("a2", "A")],
+            vec![recommendation("b1", "B"), recommendation("b2", "B")],
+        ];
+
+        let merged = merge(batches, &HashSet::new(), 3);
+
+        assert_eq!(ids(&merged), ["a1", "b1", "a2"]);
+    }
+
+    #[test]
+    fn merging_leaves_out_known_tracks_and_credits_every_seed() {
+        let known = HashSet::from([TrackId::from_id("a1".to_string()).unwrap()]);
+        let batches = vec![
+            vec![recommendation("a1", "A"), recommendation("shared", "A")],
+            vec![recommendation("shared", "B"), recommendation("b2", "B")],
+        ];
+
+        let merged = merge(batches, &known, 10);
+
+        assert_eq!(ids(&merged), ["shared", "b2"]);
+        assert_eq!(merged[0].seeds, ["A", "B"]);
+    }
+
+    #[test]
+    fn validates_targets() {
+        let targets = Targets {
+            energy: Some(0.8),
+            tempo: Some(120.0),
+            ..Default::default()
+        };
+        assert_eq!(targets.validate(), Ok(()));
+        assert_eq!(targets.features(), [("energy", 0.8), ("tempo", 120.0)]);
+
+        let targets = Targets {
+            valence: Some(1.5),
+            ..Default::default()
+        };
+        assert_eq!(
+            targets.validate().unwrap_err().to_string(),
+            "valence must be between 0 and 1, got 1.5"
+        );
+    }
+}
@@ -0,0 +1,71 @@
+use rspotify::model::TimeRange;
+
+use crate::albums::release_year;
+use crate::client::TopTracks;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReleaseEras {
+    pub time_range: TimeRange,
+    /// Number of top tracks released in each year, oldest first.
+    pub years: Vec<(i32, usize)>,
+    /// Number of top tracks released in each decade, keyed by its first year.
+    pub decades: Vec<(i32, usize)>,
+    pub median_year: Option<i32>,
+    /// Average age of the top tracks in years, relative to `current_year`.
+    pub nostalgia_index: Option<f64>,
+}
+
+fn count_by<F>(years: &[i32], key: F) -> Vec<(i32, usize)>
+where
+    F: Fn(i32) -> i32,
+{
+    let mut counts: Vec<(i32, usize)> = Vec::new();
+
+    for year in years {
+        let key = key(*year);
+        match counts.iter_mut().find(|(k, _)| *k == key) {
+            Some((_, count)) => *count += 1,
+            None => counts.push((key, 1)),
+        }
+    }
+
+    counts.sort_by_key(|(k, _)| *k);
+    counts
+}
+
+fn median(sorted_years: &[i32]) -> Option<i32> {
+    let len = sorted_years.len();
+    match len {
+        0 => None,
+        _ if len % 2 == 1 => Some(sorted_years[len / 2]),
+        _ => Some((sorted_years[len / 2 - 1] + sorted_years[len / 2]) / 2),
+    }
+}
+
+/// Computes the release year and decade distribution of the top tracks.
+///
+/// Tracks without a parseable release date are left out of every figure.
+pub fn get_release_eras(top_tracks: &TopTracks, current_year: i32) -> ReleaseEras {
+    let mut years: Vec<i32> = top_tracks
+        .tracks
+        .iter()
+        .filter_map(|track| track.release_date.as_deref().and_then(release_year))
+        .collect();
+    years.sort();
+
+    let nostalgia_index = match years.len() {
+        0 => None,
+        len => {
+            let total_age: i32 = years.iter().map(|year| current_year - year).sum();
+            Some(total_age as f64 / len as f64)
+        }
+    };
+
+    ReleaseEras {
+        time_range: top_tracks.time_range,
+        years: count_by(&years, |year| year),
+        decades: count_by(&years, |year| year - year.rem_euclid(10)),
+        median_year: median(&years),
+        nostalgia_index,
+    }
+}
@@ -0,0 +1,44 @@
+use std::time::Duration;
+
+use crossterm::event::{Event, EventStream, KeyEventKind};
+use futures::StreamExt;
+use tokio::sync::mpsc::UnboundedSender;
+
+use crate::app::Message;
+
+pub const TICK_RATE: Duration = Duration::from_millis(250);
+
+/// Everything the main loop reacts to, delivered over a single channel.
+#[derive(Debug)]
+pub enum AppEvent {
+    Terminal(Event),
+    Tick,
+    /// The result of a background task.
+    Message(Message),
+}
+
+/// Forwards terminal events and periodic ticks to `tx` until the receiver
+/// is dropped.
+pub fn spawn_event_reader(tx: UnboundedSender<AppEvent>) {
+    tokio::spawn(async move {
+        let mut events = EventStream::new();
+        let mut ticks = tokio::time::interval(TICK_RATE);
+
+        loop {
+            let event = tokio::select! {
+                _ = ticks.tick() => AppEvent::Tick,
+                event = events.next() => match event {
+                    // it's important to check KeyEventKind::Press to avoid handling key release events
+                    Some(Ok(Event::Key(key))) if key.kind != KeyEventKind::Press => continue,
+                    Some(Ok(event)) => AppEvent::Terminal(event),
+                    Some(Err(_)) => continue,
+                    None => break,
+                },
+            };
+
+            if tx.send(event).is_err() {
+                break;
+            }
+        }
+    });
+}
@@ -0,0 +1,50 @@
+use crate::graph::ArtistGraph;
+
+/// Fill colors of top artists, other artists the user listens to, and
+/// unexplored ones.
+const TOP_COLOR: &str = "#1db954";
+const LISTENED_COLOR: &str = "#a5e6bd";
+const UNEXPLORED_COLOR: &str = "#e0e0e0";
+
+/// Escapes text for use inside a quoted DOT string.
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// Renders the graph in Graphviz's DOT language, keeping the cluster, depth
+/// and popularity of every artist as attributes for other tools.
+pub fn render_graph(graph: &ArtistGraph) -> String {
+    let clusters = graph.clusters();
+    let mut output = "graph artists {\n  node [style=filled];\n".to_string();
+
+    for (node, cluster) in graph.nodes.iter().zip(clusters) {
+        let color = if node.top {
+            TOP_COLOR
+        } else if node.listened {
+            LISTENED_COLOR
+        } else {
+            UNEXPLORED_COLOR
+        };
+        output.push_str(&format!(
+            "  \"{}\" [label=\"{}\", fillcolor=\"{}\", cluster={}, depth={}, popularity={}, genres=\"{}\"];\n",
+            escape(&node.artist.id),
+            escape(&node.artist.name),
+            color,
+            cluster,
+            node.depth,
+            node.artist.popularity,
+            escape(&node.artist.genres.join(", "))
+        ));
+    }
+
+    for &(a, b) in &graph.edges {
+        output.push_str(&format!(
+            "  \"{}\" -- \"{}\";\n",
+            escape(&graph.nodes[a].artist.id),
+            escape(&graph.nodes[b].artist.id)
+        ));
+    }
+
+    output.push_str("}\n");
+    output
+}
@@ -0,0 +1,56 @@
+use crate::export::html::escape;
+use crate::graph::ArtistGraph;
+
+const KEYS: [(&str, &str); 7] = [
+    ("name", "string"),
+    ("genres", "string"),
+    ("popularity", "int"),
+    ("depth", "int"),
+    ("cluster", "int"),
+    ("top", "boolean"),
+    ("listened", "boolean"),
+];
+
+/// Renders the graph as GraphML, e.g. for Gephi or Cytoscape.
+pub fn render_graph(graph: &ArtistGraph) -> String {
+    let clusters = graph.clusters();
+    let mut output = "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">\n"
+        .to_string();
+
+    for (key, kind) in KEYS {
+        output.push_str(&format!(
+            "  <key id=\"{}\" for=\"node\" attr.name=\"{}\" attr.type=\"{}\"/>\n",
+            key, key, kind
+        ));
+    }
+    output.push_str("  <graph id=\"artists\" edgedefault=\"undirected\">\n");
+
+    for (node, cluster) in graph.nodes.iter().zip(clusters) {
+        let values = [
+            escape(&node.artist.name),
+            escape(&node.artist.genres.join(", ")),
+            node.artist.popularity.to_string(),
+            node.depth.to_string(),
+            cluster.to_string(),
+            node.top.to_string(),
+            node.listened.to_string(),
+        ];
+        output.push_str(&format!("    <node id=\"{}\">\n", escape(&node.artist.id)));
+        for ((key, _), value) in KEYS.iter().zip(values) {
+            output.push_str(&format!("      <data key=\"{}\">{}</data>\n", key, value));
+        }
+        output.push_str("    </node>\n");
+    }
+
+    for &(a, b) in &graph.edges {
+        output.push_str(&format!(
+            "    <edge source=\"{}\" target=\"{}\"/>\n",
+            escape(&graph.nodes[a].artist.id),
+            escape(&graph.nodes[b].artist.id)
+        ));
+    }
+
+    output.push_str("  </graph>\n</graphml>\n");
+    output
+}
@@ -0,0 +1,285 @@
+use crate::app::Model;
+use crate::client::{show_time_range, TopArtists, TopTracks};
+use crate::report::{get_top_genres, Report};
+
+const STYLE: &str = "body{font-family:sans-serif;background:#121212;color:#e0e0e0;\
+max-width:48rem;margin:2rem auto;padding:0 1rem}\
+h1,h2{color:#1db954}\
+section{border:1px solid #1db954;border-radius:8px;padding:0 1rem 1rem;margin-bottom:1rem}\
+li::marker{color:#1db954;font-style:italic}\
+table{border-collapse:collapse;width:100%}\
+th,td{text-align:left;padding:2px 8px}\
+td.index{color:#1db954;font-style:italic}\
+.meta{color:#9e9e9e}";
+
+/// Escapes text for use inside HTML elements and attribute values.
+pub fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+
+    escaped
+}
+
+fn page(title: &str, body: &str) -> String {
+    format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+         <title>{title}</title>\n<style>{STYLE}</style>\n</head>\n<body>\n\
+         <h1>{title}</h1>\n{body}</body>\n</html>\n",
+        title = escape(title),
+    )
+}
+
+pub fn render_report(report: &Report) -> String {
+    let mut body = String::new();
+
+    for section in report.sections() {
+        let tag = if section.ordered { "ol" } else { "ul" };
+
+        body.push_str(&format!(
+            "<section>\n<h2>{}</h2>\n<{}>\n",
+            escape(&section.title),
+            tag
+        ));
+        for line in &section.lines {
+            body.push_str(&format!("<li>{}</li>\n", escape(line)));
+        }
+        body.push_str(&format!("</{}>\n</section>\n", tag));
+    }
+
+    page(&report.title(), &body)
+}
+
+const CHART_WIDTH: usize = 480;
+const CHART_LABEL_WIDTH: usize = 180;
+const CHART_BAR_HEIGHT: usize = 18;
+const CHART_BAR_GAP: usize = 6;
+const CHART_GENRES: usize = 10;
+
+fn format_optional<T: std::fmt::Display>(value: Option<T>) -> String {
+    match value {
+        Some(value) => value.to_string(),
+        None => "-".to_string(),
+    }
+}
+
+/// Renders a horizontal bar chart of genre counts as inline SVG.
+pub fn genre_chart_svg(genres: &[(String, usize)]) -> String {
+    bar_chart_svg("Genre chart", genres)
+}
+
+/// Renders a horizontal bar chart of track counts per release decade as
+/// inline SVG.
+pub fn decade_chart_svg(decades: &[(i32, usize)]) -> String {
+    let bars: Vec<(String, usize)> = decades
+        .iter()
+        .map(|(decade, count)| (format!("{}s", decade), *count))
+        .collect();
+    bar_chart_svg("Release decade chart", &bars)
+}
+
+fn bar_chart_svg(label: &str, bars: &[(String, usize)]) -> String {
+    let max = bars.iter().map(|(_, count)| *count).max().unwrap_or(1);
+    let height = bars.len() * (CHART_BAR_HEIGHT + CHART_BAR_GAP);
+    let bar_space = CHART_WIDTH - CHART_LABEL_WIDTH - 40;
+
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{CHART_WIDTH}\" height=\"{height}\" \
+         role=\"img\" aria-label=\"{label}\">\n"
+    );
+
+    for (i, (name, count)) in bars.iter().enumerate() {
+        let y = i * (CHART_BAR_HEIGHT + CHART_BAR_GAP);
+        let width = (count * bar_space / max).max(1);
+        let text_y = y + CHART_BAR_HEIGHT - 4;
+
+        svg.push_str(&format!(
+            "<text x=\"{x}\" y=\"{text_y}\" text-anchor=\"end\" fill=\"#e0e0e0\" \
+             font-size=\"12\">{name}</text>\n\
+             <rect x=\"{CHART_LABEL_WIDTH}\" y=\"{y}\" width=\"{width}\" \
+             height=\"{CHART_BAR_HEIGHT}\" fill=\"#1db954\"/>\n\
+             <text x=\"{count_x}\" y=\"{text_y}\" fill=\"#e0e0e0\" font-size=\"12\">{count}</text>\n",
+            x = CHART_LABEL_WIDTH - 6,
+            name = escape(name),
+            count_x = CHART_LABEL_WIDTH + width + 4,
+        ));
+    }
+
+    svg.push_str("</svg>\n");
+    svg
+}
+
+fn tracks_table(top_tracks: &TopTracks) -> String {
+    let mut table = String::from(
+        "<table>\n<tr><th>#</th><th>Track</th><th>Artists</th><th>Album</th><th>Length</th></tr>\n",
+    );
+
+    for track in &top_tracks.tracks {
+        table.push_str(&format!(
+            "<tr><td class=\"index\">{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>\n",
+            track.index,
+            escape(&track.track_name),
+            escape(&track.artists.join(", ")),
+            escape(&track.album_name),
+            escape(&track.duration),
+        ));
+    }
+
+    table.push_str("</table>\n");
+    table
+}
+
+fn artists_table(top_artists: &TopArtists) -> String {
+    let mut table = String::from("<table>\n<tr><th>#</th><th>Artist</th><th>Genres</th></tr>\n");
+
+    for artist in &top_artists.artists {
+        table.push_str(&format!(
+            "<tr><td class=\"index\">{}</td><td>{}</td><td>{}</td></tr>\n",
+            artist.index,
+            escape(&artist.artist_name),
+            escape(
+                &artist
+                    .genres
+                    .iter()
+                    .take(3)
+                    .cloned()
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            ),
+        ));
+    }
+
+    table.push_str("</table>\n");
+    table
+}
+
+fn comparison_table(model: &Model) -> String {
+    let mut table = String::from(
+        "<table>\n<tr><th>Range</th><th>Artist diversity</th><th>Top-3 concentration</th>\
+         <th>Track obscurity</th><th>Median year</th><th>Nostalgia (yrs)</th></tr>\n",
+    );
+
+    for top_tracks in &model.top_tracks {
+        let time_range = top_tracks.time_range;
+        let metrics = model.metrics.iter().find(|m| m.time_range == time_range);
+        let eras = model
+            .release_eras
+            .iter()
+            .find(|e| e.time_range == time_range);
+
+        table.push_str(&format!(
+            "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>\n",
+            show_time_range(&time_range),
+            format_optional(
+                metrics
+                    .and_then(|m| m.artist_entropy)
+                    .map(|v| format!("{:.2}", v))
+            ),
+            format_optional(
+                metrics
+                    .and_then(|m| m.artist_concentration)
+                    .map(|v| format!("{:.0}%", v * 100.0))
+            ),
+            format_optional(
+                metrics
+                    .and_then(|m| m.track_obscurity)
+                    .map(|v| format!("{:.0}", v))
+            ),
+            format_optional(eras.and_then(|e| e.median_year)),
+            format_optional(
+                eras.and_then(|e| e.nostalgia_index)
+                    .map(|v| format!("{:.1}", v))
+            ),
+        ));
+    }
+
+    table.push_str("</table>\n");
+
+    let staples: Vec<String> = match model.top_tracks.split_first() {
+        Some((first, rest)) => first
+            .tracks
+            .iter()
+            .filter(|track| {
+                rest.iter().all(|other| {
+                    other
+                        .tracks
+                        .iter()
+                        .any(|t| t.track_name == track.track_name && t.artists == track.artists)
+                })
+            })
+            .map(|track| format!("{} by {}", track.track_name, track.artists.join(", ")))
+            .collect(),
+        None => Vec::new(),
+    };
+
+    table.push_str("<h3>In every range</h3>\n");
+    if staples.is_empty() {
+        table.push_str("<p>No track appears in every range.</p>\n");
+    } else {
+        table.push_str("<ul>\n");
+        for staple in staples {
+            table.push_str(&format!("<li>{}</li>\n", escape(&staple)));
+        }
+        table.push_str("</ul>\n");
+    }
+
+    table
+}
+
+/// Renders the top lists, genre and decade charts and range comparisons of
+/// the model into a single self-contained HTML page.
+///
+/// `generated` is printed verbatim so the output is reproducible.
+pub fn render_stats(model: &Model, generated: &str) -> String {
+    let mut body = format!("<p class=\"meta\">Generated {}</p>\n", escape(generated));
+
+    for top_tracks in &model.top_tracks {
+        let time_range = top_tracks.time_range;
+        body.push_str(&format!(
+            "<section>\n<h2>{}</h2>\n<h3>Top Tracks</h3>\n{}",
+            show_time_range(&time_range),
+            tracks_table(top_tracks)
+        ));
+
+        if let Some(top_artists) = model
+            .top_artists
+            .iter()
+            .find(|a| a.time_range == time_range)
+        {
+            body.push_str(&format!(
+                "<h3>Top Artists</h3>\n{}<h3>Genres</h3>\n{}",
+                artists_table(top_artists),
+                genre_chart_svg(&get_top_genres(top_artists, CHART_GENRES))
+            ));
+        }
+
+        if let Some(eras) = model
+            .release_eras
+            .iter()
+            .find(|e| e.time_range == time_range)
+        {
+            body.push_str(&format!(
+                "<h3>Release Decades</h3>\n{}",
+                decade_chart_svg(&eras.decades)
+            ));
+        }
+
+        body.push_str("</section>\n");
+    }
+
+    body.push_str(&format!(
+        "<section>\n<h2>Comparison</h2>\n{}</section>\n",
+        comparison_table(model)
+    ));
+
+    page(&format!("{}'s Spotify Stats", model.username), &body)
+}
@@ -0,0 +1,19 @@
+use crate::report::Report;
+
+pub fn render_report(report: &Report) -> String {
+    let mut output = format!("# {}\n", report.title());
+
+    for section in report.sections() {
+        output.push_str(&format!("\n## {}\n\n", section.title));
+
+        for (i, line) in section.lines.iter().enumerate() {
+            if section.ordered {
+                output.push_str(&format!("{}. {}\n", i + 1, line));
+            } else {
+                output.push_str(&format!("- {}\n", line));
+            }
+        }
+    }
+
+    output
+}
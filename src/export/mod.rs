@@ -0,0 +1,120 @@
+pub mod dot;
+pub mod graphml;
+pub mod html;
+pub mod markdown;
+pub mod svg;
+
+use std::io;
+use std::path::{Path, PathBuf};
+
+use rspotify::model::TimeRange;
+
+use crate::app::Model;
+use crate::client::time_range_slug;
+use crate::graph::ArtistGraph;
+use crate::report::Report;
+
+/// The files that can be exported from the TUI.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Export {
+    /// The open wrapped report, as Markdown and HTML.
+    Report,
+    /// The stats page of every range, as HTML.
+    Html,
+    /// The stats card of the current range, as SVG and, with the `png`
+    /// feature, PNG.
+    Card,
+    /// The open artist graph, as DOT and GraphML.
+    Graph,
+}
+
+/// Writes `export` of the model into `dir`, returning the paths.
+pub fn write(model: &Model, export: Export, dir: &Path) -> io::Result<Vec<PathBuf>> {
+    match export {
+        Export::Report => match &model.report {
+            Some(report) => write_report(report, dir),
+            None => Err(io::Error::other("no report is open")),
+        },
+        Export::Html => {
+            let generated = chrono::Local::now().format("%Y-%m-%d %H:%M").to_string();
+            Ok(vec![write_stats_html(model, &generated, dir)?])
+        }
+        Export::Card => write_card(model, model.time_range, cfg!(feature = "png"), dir),
+        Export::Graph => match model.graph.as_ref().and_then(|view| view.graph.as_ref()) {
+            Some((graph, _)) => write_graph(graph, dir),
+            None => Err(io::Error::other("no artist graph is loaded")),
+        },
+    }
+}
+
+/// Writes the report as Markdown and HTML into `dir`, returning the paths.
+pub fn write_report(report: &Report, dir: &Path) -> io::Result<Vec<PathBuf>> {
+    let name = format!(
+        "spotifystats-wrapped-{}",
+        time_range_slug(&report.time_range)
+    );
+
+    let markdown_path = dir.join(format!("{}.md", name));
+    std::fs::write(&markdown_path, markdown::render_report(report))?;
+
+    let html_path = dir.join(format!("{}.html", name));
+    std::fs::write(&html_path, html::render_report(report))?;
+
+    Ok(vec![markdown_path, html_path])
+}
+
+/// Writes the self-contained HTML stats page into `dir`, returning the path.
+pub fn write_stats_html(model: &Model, generated: &str, dir: &Path) -> io::Result<PathBuf> {
+    let path = dir.join("spotifystats.html");
+    std::fs::write(&path, html::render_stats(model, generated))?;
+
+    Ok(path)
+}
+
+/// Writes the shareable stats card of `time_range` as SVG, and as PNG when
+/// `png` is set, into `dir`, returning the paths.
+pub fn write_card(
+    model: &Model,
+    time_range: TimeRange,
+    png: bool,
+    dir: &Path,
+) -> io::Result<Vec<PathBuf>> {
+    let name = format!("spotifystats-card-{}", time_range_slug(&time_range));
+    let card = svg::render_card(model, time_range, &svg::CardTheme::from_theme(&model.theme));
+
+    let svg_path = dir.join(format!("{}.svg", name));
+    std::fs::write(&svg_path, &card)?;
+    let mut paths = vec![svg_path];
+
+    if png {
+        let png_path = dir.join(format!("{}.png", name));
+        std::fs::write(&png_path, rasterize(&card)?)?;
+        paths.push(png_path);
+    }
+
+    Ok(paths)
+}
+
+/// Writes the artist graph as DOT and GraphML into `dir`, returning the
+/// paths.
+pub fn write_graph(graph: &ArtistGraph, dir: &Path) -> io::Result<Vec<PathBuf>> {
+    let dot_path = dir.join("spotifystats-artists.dot");
+    std::fs::write(&dot_path, dot::render_graph(graph))?;
+
+    let graphml_path = dir.join("spotifystats-artists.graphml");
+    std::fs::write(&graphml_path, graphml::render_graph(graph))?;
+
+    Ok(vec![dot_path, graphml_path])
+}
+
+#[cfg(feature = "png")]
+fn rasterize(card: &str) -> io::Result<Vec<u8>> {
+    svg::render_png(card)
+}
+
+#[cfg(not(feature = "png"))]
+fn rasterize(_: &str) -> io::Result<Vec<u8>> {
+    Err(io::Error::other(
+        "PNG export requires building with the `png` feature",
+    ))
+}
@@ -0,0 +1,365 @@
+use ratatui::style::Color;
+use rspotify::model::TimeRange;
+
+use crate::app::Model;
+use crate::client::show_time_range;
+use crate::export::html::escape;
+use crate::report::get_top_genres;
+use crate::theme::Theme;
+
+pub const CARD_WIDTH: u32 = 1200;
+pub const CARD_HEIGHT: u32 = 630;
+
+const CARD_ITEMS: usize = 5;
+const CARD_GENRES: usize = 6;
+
+/// Colors of the stats card, as SVG colors.
+pub struct CardTheme {
+    pub background: String,
+    pub foreground: String,
+    pub accent: String,
+    pub muted: String,
+}
+
+impl CardTheme {
+    /// The card colors matching the terminal colors of `theme`. Without a
+    /// background color, like in the monochrome theme used for `NO_COLOR`,
+    /// the card is white, and text without a color is black or white,
+    /// whichever stands out on the background.
+    pub fn from_theme(theme: &Theme) -> CardTheme {
+        let background = rgb(theme.background).unwrap_or(WHITE);
+        let plain = if is_light(background) { BLACK } else { WHITE };
+        let foreground = theme.value.fg.and_then(rgb).unwrap_or(plain);
+        let accent = theme.header.fg.and_then(rgb).unwrap_or(foreground);
+        let muted = theme.muted.fg.and_then(rgb).unwrap_or(GRAY);
+
+        CardTheme {
+            background: hex(background),
+            foreground: hex(foreground),
+            accent: hex(accent),
+            muted: hex(muted),
+        }
+    }
+}
+
+type Rgb = (u8, u8, u8);
+
+const BLACK: Rgb = (0, 0, 0);
+const WHITE: Rgb = (255, 255, 255);
+const GRAY: Rgb = (127, 127, 127);
+
+/// The usual xterm values of the 16 ANSI colors.
+const ANSI_COLORS: [Rgb; 16] = [
+    BLACK,
+    (205, 0, 0),
+    (0, 205, 0),
+    (205, 205, 0),
+    (0, 0, 238),
+    (205, 0, 205),
+    (0, 205, 205),
+    (229, 229, 229),
+    GRAY,
+    (255, 0, 0),
+    (0, 255, 0),
+    (255, 255, 0),
+    (92, 92, 255),
+    (255, 0, 255),
+    (0, 255, 255),
+    WHITE,
+];
+
+/// `color` as red, green and blue, or `None` for the terminal's default
+/// color.
+fn rgb(color: Color) -> Option<Rgb> {
+    let index = match color {
+        Color::Reset => return None,
+        Color::Rgb(r, g, b) => return Some((r, g, b)),
+        Color::Indexed(index) => index,
+        Color::Black => 0,
+        Color::Red => 1,
+        Color::Green => 2,
+        Color::Yellow => 3,
+        Color::Blue => 4,
+        Color::Magenta => 5,
+        Color::Cyan => 6,
+        Color::Gray => 7,
+        Color::DarkGray => 8,
+        Color::LightRed => 9,
+        Color::LightGreen => 10,
+        Color::LightYellow => 11,
+        Color::LightBlue => 12,
+        Color::LightMagenta => 13,
+        Color::LightCyan => 14,
+        Color::White => 15,
+    };
+
+    Some(match index {
+        0..=15 => ANSI_COLORS[usize::from(index)],
+        // A 6x6x6 color cube followed by 24 shades of gray.
+        16..=231 => {
+            let level = |n: u8| if n == 0 { 0 } else { 55 + n * 40 };
+            let n = index - 16;
+            (level(n / 36), level(n / 6 % 6), level(n % 6))
+        }
+        232..=255 => {
+            let gray = 8 + (index - 232) * 10;
+            (gray, gray, gray)
+        }
+    })
+}
+
+fn hex((r, g, b): Rgb) -> String {
+    format!("#{r:02x}{g:02x}{b:02x}")
+}
+
+/// Whether dark text reads better than light text on `color`.
+fn is_light((r, g, b): Rgb) -> bool {
+    let luma = 299 * u32::from(r) + 587 * u32::from(g) + 114 * u32::from(b);
+    luma > 128_000
+}
+
+fn truncate(text: &str, max_chars: usize) -> String {
+    if text.chars().count() <= max_chars {
+        text.to_string()
+    } else {
+        let truncated: String = text.chars().take(max_chars - 1).collect();
+        format!("{}…", truncated)
+    }
+}
+
+fn text(x: u32, y: u32, size: u32, fill: &str, extra: &str, content: &str) -> String {
+    format!(
+        "<text x=\"{x}\" y=\"{y}\" font-size=\"{size}\" fill=\"{fill}\" {extra}>{}</text>\n",
+        escape(content)
+    )
+}
+
+/// A ranked column of `(title, subtitle)` pairs.
+fn ranked_column(
+    theme: &CardTheme,
+    x: u32,
+    heading: &str,
+    items: &[(String, String)],
+    max_chars: usize,
+) -> String {
+    let mut svg = text(x, 150, 26, &theme.accent, "font-weight=\"bold\"", heading);
+
+    for (i, (title, subtitle)) in items.iter().enumerate() {
+        let y = 200 + i as u32 * 76;
+
+        svg.push_str(&text(
+            x,
+            y,
+            24,
+            &theme.accent,
+            "font-style=\"italic\"",
+            &(i + 1).to_string(),
+        ));
+        svg.push_str(&text(
+            x + 32,
+            y,
+            24,
+            &theme.foreground,
+            "",
+            &truncate(title, max_chars),
+        ));
+        svg.push_str(&text(
+            x + 32,
+            y + 26,
+            18,
+            &theme.muted,
+            "",
+            &truncate(subtitle, max_chars + 6),
+        ));
+    }
+
+    svg
+}
+
+fn genre_column(theme: &CardTheme, x: u32, genres: &[(String, usize)]) -> String {
+    let mut svg = text(x, 150, 26, &theme.accent, "font-weight=\"bold\"", "Genres");
+    let max = genres.iter().map(|(_, count)| *count).max().unwrap_or(1);
+
+    for (i, (genre, count)) in genres.iter().enumerate() {
+        let y = 180 + i as u32 * 62;
+        let width = (*count as u32 * 260 / max as u32).max(4);
+
+        svg.push_str(&text(
+            x,
+            y + 16,
+            18,
+            &theme.foreground,
+            "",
+            &truncate(genre, 24),
+        ));
+        svg.push_str(&format!(
+            "<rect x=\"{x}\" y=\"{}\" width=\"{width}\" height=\"14\" rx=\"4\" fill=\"{}\"/>\n",
+            y + 26,
+            theme.accent
+        ));
+    }
+
+    svg
+}
+
+/// Renders a shareable card with the top 5 tracks, top 5 artists and genre
+/// breakdown of `time_range`.
+pub fn render_card(model: &Model, time_range: TimeRange, theme: &CardTheme) -> String {
+    let tracks: Vec<(String, String)> = model
+        .top_tracks
+        .iter()
+        .find(|t| t.time_range == time_range)
+        .map(|t| {
+            t.tracks
+                .iter()
+                .take(CARD_ITEMS)
+                .map(|track| (track.track_name.clone(), track.artists.join(", ")))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let top_artists = model
+        .top_artists
+        .iter()
+        .find(|a| a.time_range == time_range);
+    let artists: Vec<(String, String)> = top_artists
+        .map(|a| {
+            a.artists
+                .iter()
+                .take(CARD_ITEMS)
+                .map(|artist| {
+                    let genres = artist
+                        .genres
+                        .iter()
+                        .take(2)
+                        .cloned()
+                        .collect::<Vec<String>>();
+                    (artist.artist_name.clone(), genres.join(", "))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    let genres = top_artists
+        .map(|a| get_top_genres(a, CARD_GENRES))
+        .unwrap_or_default();
+
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{CARD_WIDTH}\" height=\"{CARD_HEIGHT}\" \
+         viewBox=\"0 0 {CARD_WIDTH} {CARD_HEIGHT}\" font-family=\"sans-serif\">\n\
+         <rect width=\"{CARD_WIDTH}\" height=\"{CARD_HEIGHT}\" fill=\"{}\"/>\n\
+         <rect x=\"12\" y=\"12\" width=\"{}\" height=\"{}\" rx=\"16\" fill=\"none\" \
+         stroke=\"{}\" stroke-width=\"4\"/>\n",
+        theme.background,
+        CARD_WIDTH - 24,
+        CARD_HEIGHT - 24,
+        theme.accent,
+    );
+
+    svg.push_str(&text(
+        60,
+        90,
+        40,
+        &theme.accent,
+        "font-weight=\"bold\"",
+        &format!(
+            "{}'s Top 5 ({})",
+            truncate(&model.username, 32),
+            show_time_range(&time_range)
+        ),
+    ));
+    svg.push_str(&ranked_column(theme, 60, "Top Tracks", &tracks, 26));
+    svg.push_str(&ranked_column(theme, 500, "Top Artists", &artists, 20));
+    svg.push_str(&genre_column(theme, 860, &genres));
+    svg.push_str("</svg>\n");
+
+    svg
+}
+
+/// Rasterizes an SVG document into a PNG image.
+#[cfg(feature = "png")]
+pub fn render_png(svg: &str) -> std::io::Result<Vec<u8>> {
+    use resvg::{tiny_skia, usvg};
+
+    let mut options = usvg::Options::default();
+    options.fontdb_mut().load_system_fonts();
+
+    let tree = usvg::Tree::from_str(svg, &options).map_err(std::io::Error::other)?;
+    let mut pixmap = tiny_skia::Pixmap::new(CARD_WIDTH, CARD_HEIGHT)
+        .ok_or_else(|| std::io::Error::other("Invalid card size"))?;
+
+    resvg::render(&tree, tiny_skia::Transform::default(), &mut pixmap.as_mut());
+    pixmap.encode_png().map_err(std::io::Error::other)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::{TopTrack, TopTracks};
+    use crate::theme::{ThemeColors, BUILTIN_THEMES};
+
+    fn model(theme: Theme) -> Model {
+        let mut model = Model::new();
+        model.theme = theme;
+        model.top_tracks = vec![TopTracks {
+            time_range: TimeRange::ShortTerm,
+            tracks: vec![TopTrack {
+                index: 1,
+                id: None,
+                track_name: "Hyperballad".to_string(),
+                duration: "5:21".to_string(),
+                duration_ms: 321_000,
+                artists: vec!["Björk".to_string()],
+                album_id: None,
+                album_name: "Post".to_string(),
+                album_artists: vec!["Björk".to_string()],
+                release_date: Some("1995".to_string()),
+                popularity: 70,
+            }],
+        }];
+        model
+    }
+
+    #[test]
+    fn cards_are_readable_in_every_builtin_theme() {
+        let expected = [
+            ("dark", "#000000", "#ffffff"),
+            ("light", "#ffffff", "#000000"),
+            ("high-contrast", "#000000", "#ffffff"),
+            ("monochrome", "#ffffff", "#000000"),
+        ];
+        assert_eq!(expected.map(|(name, _, _)| name), BUILTIN_THEMES);
+
+        for (name, background, foreground) in expected {
+            let theme = Theme::builtin(name).unwrap();
+            let card = render_card(
+                &model(theme),
+                TimeRange::ShortTerm,
+                &CardTheme::from_theme(&theme),
+            );
+
+            assert!(
+                card.contains(&format!(
+                    "<rect width=\"{CARD_WIDTH}\" height=\"{CARD_HEIGHT}\" fill=\"{background}\"/>"
+                )),
+                "{name} background"
+            );
+            assert!(
+                card.contains(&format!("fill=\"{foreground}\" >Hyperballad</text>")),
+                "{name} foreground"
+            );
+        }
+    }
+
+    #[test]
+    fn custom_themes_set_the_card_background() {
+        let colors = ThemeColors {
+            background: Some("#001b2e".to_string()),
+            ..Default::default()
+        };
+        let theme = Theme::monochrome().with_colors(&colors).unwrap();
+
+        let card = CardTheme::from_theme(&theme);
+        assert_eq!(card.background, "#001b2e");
+        assert_eq!(card.foreground, "#ffffff");
+    }
+}
@@ -0,0 +1,120 @@
+//! A local stand-in for the Spotify Web API, for tests of code that sends
+//! requests through a real client.
+
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::{Arc, Mutex};
+
+use chrono::{Duration, Utc};
+use rspotify::{AuthCodeSpotify, Config, Credentials, OAuth, Token};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Request {
+    pub method: String,
+    /// The path below the API base, with the query.
+    pub path: String,
+    pub body: String,
+}
+
+/// A canned JSON answer to the request with this method and path.
+pub type Route = (&'static str, &'static str, &'static str);
+
+/// Records every request and answers those matching one of its routes with
+/// the route's JSON, and anything else with `204 No Content`.
+pub struct FakeSpotify {
+    url: String,
+    requests: Arc<Mutex<Vec<Request>>>,
+}
+
+impl FakeSpotify {
+    pub fn start(routes: &'static [Route]) -> FakeSpotify {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/v1/", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(Vec::new()));
+
+        let recorded = requests.clone();
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let recorded = recorded.clone();
+                std::thread::spawn(move || serve(stream.unwrap(), routes, recorded));
+            }
+        });
+
+        FakeSpotify { url, requests }
+    }
+
+    pub fn client(&self) -> AuthCodeSpotify {
+        let token = Token {
+            access_token: "test-token".to_string(),
+            expires_in: Duration::hours(1),
+            expires_at: Some(Utc::now() + Duration::hours(1)),
+            ..Default::default()
+        };
+        let config = Config {
+            api_base_url: self.url.clone(),
+            ..Default::default()
+        };
+        AuthCodeSpotify::from_token_with_config(
+            token,
+            Credentials::default(),
+            OAuth::default(),
+            config,
+        )
+    }
+
+    pub fn requests(&self) -> Vec<Request> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+/// Answers requests on one connection until the client closes it.
+fn serve(stream: TcpStream, routes: &[Route], requests: Arc<Mutex<Vec<Request>>>) {
+    let mut reader = BufReader::new(stream.try_clone().unwrap());
+    let mut stream = stream;
+
+    loop {
+        let mut request_line = String::new();
+        if reader.read_line(&mut request_line).unwrap_or(0) == 0 {
+            return;
+        }
+        let mut parts = request_line.split_whitespace();
+        let method = parts.next().unwrap_or_default().to_string();
+        let target = parts.next().unwrap_or_default();
+        let path = target.strip_prefix("/v1/").unwrap_or(target).to_string();
+
+        let mut content_length = 0;
+        loop {
+            let mut header = String::new();
+            reader.read_line(&mut header).unwrap();
+            let header = header.trim_end();
+            if header.is_empty() {
+                break;
+            }
+            if let Some((name, value)) = header.split_once(':') {
+                if name.eq_ignore_ascii_case("content-length") {
+                    content_length = value.trim().parse().unwrap();
+                }
+            }
+        }
+        let mut body = vec![0; content_length];
+        reader.read_exact(&mut body).unwrap();
+
+        let route = routes
+            .iter()
+            .find(|(route_method, route_path, _)| *route_method == method && *route_path == path);
+        let response = match route {
+            Some((_, _, json)) => format!(
+                "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{}",
+                json.len(),
+                json
+            ),
+            None => "HTTP/1.1 204 No Content\r\nContent-Length: 0\r\n\r\n".to_string(),
+        };
+        requests.lock().unwrap().push(Request {
+            method,
+            path,
+            body: String::from_utf8(body).unwrap(),
+        });
+        stream.write_all(response.as_bytes()).unwrap();
+    }
+}
@@ -0,0 +1,665 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use rspotify::clients::BaseClient;
+use rspotify::model::{ArtistId, FullArtist, Id};
+use rspotify::{AuthCodeSpotify, ClientError};
+use serde::{Deserialize, Serialize};
+
+use crate::client::TopArtist;
+use crate::state::{self, StateError};
+
+/// How many times every artist may move between clusters before the
+/// clustering settles for what it has.
+const MAX_ROUNDS: usize = 20;
+/// How many bridge artists the summary lists.
+const BRIDGE_LIMIT: usize = 10;
+/// How many unexplored neighbors the summary lists.
+const NEIGHBOR_LIMIT: usize = 25;
+
+/// Settings of the artist graph, read from the `[graph]` section of the
+/// config.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct GraphOptions {
+    /// How many steps of related artists to follow from the top artists,
+    /// between 1 and 3.
+    pub depth: usize,
+    /// How many artists the graph holds at most. Related artists beyond it
+    /// are left out, which bounds the number of requests.
+    pub max_artists: usize,
+    /// How many days fetched related artists are reused for.
+    pub cache_days: u32,
+}
+
+impl Default for GraphOptions {
+    fn default() -> Self {
+        GraphOptions {
+            depth: 1,
+            max_artists: 150,
+            cache_days: 7,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DepthError(pub usize);
+
+impl fmt::Display for DepthError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "depth must be between 1 and 3, got {}", self.0)
+    }
+}
+
+impl std::error::Error for DepthError {}
+
+impl GraphOptions {
+    pub fn validate(&self) -> Result<(), DepthError> {
+        match self.depth {
+            1..=3 => Ok(()),
+            depth => Err(DepthError(depth)),
+        }
+    }
+}
+
+/// An artist as stored in the graph and in the cache.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GraphArtist {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub genres: Vec<String>,
+    #[serde(default)]
+    pub popularity: u32,
+}
+
+impl From<FullArtist> for GraphArtist {
+    fn from(artist: FullArtist) -> Self {
+        GraphArtist {
+            id: artist.id.id().to_string(),
+            name: artist.name,
+            genres: artist.genres,
+            popularity: artist.popularity,
+        }
+    }
+}
+
+impl From<&TopArtist> for GraphArtist {
+    fn from(artist: &TopArtist) -> Self {
+        GraphArtist {
+            id: artist.id.id().to_string(),
+            name: artist.artist_name.clone(),
+            genres: artist.genres.clone(),
+            popularity: artist.popularity,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct GraphNode {
+    pub artist: GraphArtist,
+    /// How many steps of related artists it is from the top artists.
+    pub depth: usize,
+    /// Whether it is one of the top artists the graph started from.
+    pub top: bool,
+    /// Whether the user listens to it, in the top artists or the artists of
+    /// the top tracks.
+    pub listened: bool,
+}
+
+/// Top artists and their related artists, linked both ways.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ArtistGraph {
+    pub nodes: Vec<GraphNode>,
+    /// Pairs of node indices, the smaller one first.
+    pub edges: BTreeSet<(usize, usize)>,
+    index: HashMap<String, usize>,
+    listened: HashSet<String>,
+}
+
+impl ArtistGraph {
+    /// A graph of just the `top` artists. `listened` holds the names of other
+    /// artists the user listens to, which are not counted as unexplored.
+    pub fn new(top: Vec<GraphArtist>, listened: HashSet<String>) -> ArtistGraph {
+        let mut graph = ArtistGraph {
+            listened,
+            ..Default::default()
+        };
+        for artist in top {
+            graph.add_node(artist, 0, true);
+        }
+        graph
+    }
+
+    fn add_node(&mut self, artist: GraphArtist, depth: usize, top: bool) -> usize {
+        if let Some(&i) = self.index.get(&artist.id) {
+            return i;
+        }
+
+        let i = self.nodes.len();
+        self.index.insert(artist.id.clone(), i);
+        self.nodes.push(GraphNode {
+            listened: top || self.listened.contains(&artist.name),
+            artist,
+            depth,
+            top,
+        });
+        i
+    }
+
+    /// Links the artist `id` to each of `related`, adding those not in the
+    /// graph yet while it holds fewer than `max_artists`.
+    pub fn expand(&mut self, id: &str, related: Vec<GraphArtist>, max_artists: usize) {
+        let Some(&from) = self.index.get(id) else {
+            return;
+        };
+        let depth = self.nodes[from].depth + 1;
+
+        for artist in related {
+            let to = match self.index.get(&artist.id) {
+                Some(&to) => to,
+                None if self.nodes.len() < max_artists => self.add_node(artist, depth, false),
+                None => continue,
+            };
+            if from != to {
+                self.edges.insert((from.min(to), from.max(to)));
+            }
+        }
+    }
+
+    fn adjacency(&self) -> Vec<Vec<usize>> {
+        let mut adjacency = vec![Vec::new(); self.nodes.len()];
+        for &(a, b) in &self.edges {
+            adjacency[a].push(b);
+            adjacency[b].push(a);
+        }
+        adjacency
+    }
+
+    /// The cluster of every node, numbered from the largest cluster down.
+    ///
+    /// Each artist in turn joins the cluster of its neighbors that gains the
+    /// most modularity, until none of them moves, which is the first phase
+    /// of the Louvain method.
+    pub fn clusters(&self) -> Vec<usize> {
+        let adjacency = self.adjacency();
+        let degree: Vec<f64> = adjacency.iter().map(|n| n.len() as f64).collect();
+        let twice_edges = 2.0 * self.edges.len() as f64;
+        let mut cluster: Vec<usize> = (0..self.nodes.len()).collect();
+        let mut total = degree.clone();
+
+        for _ in 0..MAX_ROUNDS {
+            if twice_edges == 0.0 {
+                break;
+            }
+            let mut moved = false;
+
+            for i in 0..self.nodes.len() {
+                let own = cluster[i];
+                total[own] -= degree[i];
+
+                let mut links: BTreeMap<usize, f64> = BTreeMap::new();
+                for &j in &adjacency[i] {
+                    *links.entry(cluster[j]).or_default() += 1.0;
+                }
+                let gain = |c: usize, links: f64| links - degree[i] * total[c] / twice_edges;
+
+                let mut best = own;
+                let mut best_gain = gain(own, links.get(&own).copied().unwrap_or(0.0));
+                for (&c, &count) in &links {
+                    let candidate = gain(c, count);
+                    if candidate > best_gain + f64::EPSILON {
+                        best = c;
+                        best_gain = candidate;
+                    }
+                }
+
+                total[best] += degree[i];
+                if best != own {
+                    cluster[i] = best;
+                    moved = true;
+                }
+            }
+
+            if !moved {
+                break;
+            }
+        }
+
+        // Renumber by size, keeping the order of first appearance for ties.
+        let mut sizes: Vec<(usize, usize, usize)> = Vec::new();
+        for (i, &c) in cluster.iter().enumerate() {
+            match sizes.iter_mut().find(|(label, _, _)| *label == c) {
+                Some((_, size, _)) => *size += 1,
+                None => sizes.push((c, 1, i)),
+            }
+        }
+        sizes.sort_by(|a, b| b.1.cmp(&a.1).then(a.2.cmp(&b.2)));
+        let numbers: HashMap<usize, usize> = sizes
+            .iter()
+            .enumerate()
+            .map(|(number, (label, _, _))| (*label, number))
+            .collect();
+
+        cluster.iter().map(|c| numbers[c]).collect()
+    }
+
+    /// The clusters, bridge artists and unexplored neighbors of the graph.
+    pub fn summary(&self) -> GraphSummary {
+        let adjacency = self.adjacency();
+        let cluster = self.clusters();
+        let count = cluster.iter().max().map_or(0, |max| max + 1);
+
+        let mut members: Vec<Vec<usize>> = vec![Vec::new(); count];
+        for (i, &c) in cluster.iter().enumerate() {
+            members[c].push(i);
+        }
+        let names: Vec<String> = members
+            .iter()
+            .map(|members| self.cluster_name(members, &adjacency))
+            .collect();
+
+        let clusters = members
+            .iter()
+            .zip(&names)
+            .filter(|(members, _)| members.len() > 1)
+            .map(|(members, name)| {
+                let mut members = members.clone();
+                members
+                    .sort_by_key(|&i| (!self.nodes[i].top, std::cmp::Reverse(adjacency[i].len())));
+                Cluster {
+                    name: name.clone(),
+                    artists: members
+                        .iter()
+                        .map(|&i| self.nodes[i].artist.name.clone())
+                        .collect(),
+                    top: members.iter().filter(|&&i| self.nodes[i].top).count(),
+                }
+            })
+            .collect();
+
+        let mut bridges: Vec<(usize, BTreeSet<usize>, usize)> = (0..self.nodes.len())
+            .filter_map(|i| {
+                let others: Vec<usize> = adjacency[i]
+                    .iter()
+                    .map(|&j| cluster[j])
+                    .filter(|&c| c != cluster[i] && members[c].len() > 1)
+                    .collect();
+                let linked: BTreeSet<usize> = others.iter().copied().collect();
+                (!linked.is_empty() && members[cluster[i]].len() > 1).then_some((
+                    i,
+                    linked,
+                    others.len(),
+                ))
+            })
+            .collect();
+        bridges.sort_by(|a, b| b.1.len().cmp(&a.1.len()).then(b.2.cmp(&a.2)));
+        let bridges = bridges
+            .into_iter()
+            .take(BRIDGE_LIMIT)
+            .map(|(i, linked, _)| Bridge {
+                artist: self.nodes[i].artist.name.clone(),
+                clusters: std::iter::once(cluster[i])
+                    .chain(linked)
+                    .map(|c| names[c].clone())
+                    .collect(),
+            })
+            .collect();
+
+        let mut unexplored: Vec<Neighbor> = (0..self.nodes.len())
+            .filter(|&i| !self.nodes[i].listened)
+            .map(|i| Neighbor {
+                artist: self.nodes[i].artist.name.clone(),
+                popularity: self.nodes[i].artist.popularity,
+                related_to: adjacency[i]
+                    .iter()
+                    .filter(|&&j| self.nodes[j].top)
+                    .map(|&j| self.nodes[j].artist.name.clone())
+                    .collect(),
+            })
+            .collect();
+        unexplored.sort_by(|a, b| {
+            b.related_to
+                .len()
+                .cmp(&a.related_to.len())
+                .then(b.popularity.cmp(&a.popularity))
+                .then(a.artist.cmp(&b.artist))
+        });
+        unexplored.truncate(NEIGHBOR_LIMIT);
+
+        GraphSummary {
+            artists: self.nodes.len(),
+            links: self.edges.len(),
+            clusters,
+            bridges,
+            unexplored,
+        }
+    }
+
+    /// The most common genre of the members, or the best linked member when
+    /// none of them has a genre.
+    fn cluster_name(&self, members: &[usize], adjacency: &[Vec<usize>]) -> String {
+        let mut genres: BTreeMap<&str, usize> = BTreeMap::new();
+        for &i in members {
+            for genre in &self.nodes[i].artist.genres {
+                *genres.entry(genre).or_default() += 1;
+            }
+        }
+
+        let genre = genres
+            .into_iter()
+            .max_by(|a, b| a.1.cmp(&b.1).then(b.0.cmp(a.0)))
+            .map(|(genre, _)| genre.to_string());
+        genre.unwrap_or_else(|| {
+            let best = members
+                .iter()
+                .max_by_key(|&&i| adjacency[i].len())
+                .copied()
+                .unwrap_or_default();
+            self.nodes[best].artist.name.clone()
+        })
+    }
+}
+
+/// Artists that relate more to each other than to the rest of the graph.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cluster {
+    /// The most common genre in the cluster.
+    pub name: String,
+    /// Top artists first, then by how many links they have.
+    pub artists: Vec<String>,
+    /// How many of them are top artists.
+    pub top: usize,
+}
+
+/// An artist related to artists of other clusters.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Bridge {
+    pub artist: String,
+    /// Its own cluster first, then the ones it links to.
+    pub clusters: Vec<String>,
+}
+
+/// An artist in the graph that the user doesn't listen to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Neighbor {
+    pub artist: String,
+    pub popularity: u32,
+    /// The top artists it is related to.
+    pub related_to: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GraphSummary {
+    pub artists: usize,
+    pub links: usize,
+    pub clusters: Vec<Cluster>,
+    /// The artists linking the most clusters first.
+    pub bridges: Vec<Bridge>,
+    /// The neighbors related to the most top artists first.
+    pub unexplored: Vec<Neighbor>,
+}
+
+impl GraphSummary {
+    /// The summary as plain lines, as printed by the `graph` subcommand.
+    pub fn lines(&self) -> Vec<String> {
+        let mut lines = vec![format!("{} artists, {} links", self.artists, self.links)];
+
+        lines.push(String::new());
+        lines.push("Clusters".to_string());
+        for cluster in &self.clusters {
+            lines.push(format!(
+                "  {} ({} artists): {}",
+                cluster.name,
+                cluster.artists.len(),
+                cluster
+                    .artists
+                    .iter()
+                    .take(5)
+                    .cloned()
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ));
+        }
+
+        lines.push(String::new());
+        lines.push("Bridge artists".to_string());
+        for bridge in &self.bridges {
+            lines.push(format!(
+                "  {}: {}",
+                bridge.artist,
+                bridge.clusters.join(" ↔ ")
+            ));
+        }
+
+        lines.push(String::new());
+        lines.push("Unexplored neighbors".to_string());
+        for neighbor in &self.unexplored {
+            lines.push(format!(
+                "  {}, related to {}",
+                neighbor.artist,
+                neighbor.related_to.join(", ")
+            ));
+        }
+
+        lines
+    }
+}
+
+/// Related artists fetched before, by the id of the artist they relate to.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RelatedCache {
+    #[serde(default)]
+    pub artists: BTreeMap<String, CachedRelated>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CachedRelated {
+    /// When they were fetched, in seconds since the Unix epoch.
+    pub fetched_at: i64,
+    pub related: Vec<GraphArtist>,
+}
+
+/// `$XDG_CACHE_HOME/spotifystats/related-artists.toml` or the platform
+/// equivalent.
+pub fn default_cache_path() -> Option<PathBuf> {
+    dirs::cache_dir().map(|dir| dir.join("spotifystats").join("related-artists.toml"))
+}
+
+impl RelatedCache {
+    /// Reads the cache at `path`. A missing or unreadable cache is empty,
+    /// since everything in it can be fetched again.
+    pub fn load(path: &Path) -> RelatedCache {
+        state::load(path).unwrap_or_default()
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), StateError> {
+        state::save(self, path)
+    }
+
+    /// The related artists of `id`, unless they are older than `max_age`
+    /// seconds at `now`.
+    pub fn get(&self, id: &str, now: i64, max_age: i64) -> Option<&[GraphArtist]> {
+        let cached = self.artists.get(id)?;
+        (now - cached.fetched_at <= max_age).then_some(cached.related.as_slice())
+    }
+
+    pub fn insert(&mut self, id: String, related: Vec<GraphArtist>, now: i64) {
+        let cached = CachedRelated {
+            fetched_at: now,
+            related,
+        };
+        self.artists.insert(id, cached);
+    }
+
+    /// Drops everything older than `max_age` seconds at `now`.
+    pub fn prune(&mut self, now: i64, max_age: i64) {
+        self.artists
+            .retain(|_, cached| now - cached.fetched_at <= max_age);
+    }
+}
+
+/// Builds the graph from `top` by following related artists to the depth of
+/// `options`, taking them from `cache` when it has them and adding those it
+/// had to fetch. `now` is in seconds since the Unix epoch.
+pub async fn get_graph(
+    client: &AuthCodeSpotify,
+    top: Vec<GraphArtist>,
+    listened: HashSet<String>,
+    options: &GraphOptions,
+    cache: &mut RelatedCache,
+    now: i64,
+) -> Result<ArtistGraph, ClientError> {
+    let max_age = i64::from(options.cache_days) * 24 * 60 * 60;
+    let mut graph = ArtistGraph::new(top, listened);
+
+    for depth in 0..options.depth {
+        let frontier: Vec<String> = graph
+            .nodes
+            .iter()
+            .filter(|node| node.depth == depth)
+            .map(|node| node.artist.id.clone())
+            .collect();
+
+        for id in frontier {
+            let related = match cache.get(&id, now, max_age) {
+                Some(related) => related.to_vec(),
+                None => {
+                    let Ok(artist_id) = ArtistId::from_id(id.as_str()) else {
+                        continue;
+                    };
+                    let related: Vec<GraphArtist> = client
+                        .artist_related_artists(artist_id)
+                        .await?
+                        .into_iter()
+                        .map(GraphArtist::from)
+                        .collect();
+                    cache.insert(id.clone(), related.clone(), now);
+                    related
+                }
+            };
+            graph.expand(&id, related, options.max_artists);
+        }
+    }
+
+    cache.prune(now, max_age);
+    Ok(graph)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::export::{dot, graphml};
+
+    fn artist(id: &str, genres: &[&str]) -> GraphArtist {
+        GraphArtist {
+            id: id.to_string(),
+            name: id.to_uppercase(),
+            genres: genres.iter().map(|g| g.to_string()).collect(),
+            popularity: 50,
+        }
+    }
+
+    /// Two triangles, `a b c` in rock and `d e f` in jazz, joined by `c-d`.
+    fn two_triangles() -> ArtistGraph {
+        let mut graph = ArtistGraph::new(
+            vec![artist("a", &["rock"]), artist("d", &["jazz"])],
+            HashSet::new(),
+        );
+        let rock = [artist("b", &["rock"]), artist("c", &["rock"])];
+        let jazz = [artist("e", &["jazz"]), artist("f", &["jazz"])];
+        graph.expand("a", rock.to_vec(), 100);
+        graph.expand("d", jazz.to_vec(), 100);
+        graph.expand("b", vec![artist("c", &["rock"])], 100);
+        graph.expand("e", vec![artist("f", &["jazz"])], 100);
+        graph.expand("c", vec![artist("d", &["jazz"])], 100);
+        graph
+    }
+
+    #[test]
+    fn expanding_stops_adding_artists_at_the_limit() {
+        let mut graph = ArtistGraph::new(vec![artist("a", &[])], HashSet::new());
+
+        graph.expand("a", vec![artist("b", &[]), artist("c", &[])], 2);
+        graph.expand("b", vec![artist("a", &[]), artist("c", &[])], 2);
+
+        let ids: Vec<&str> = graph.nodes.iter().map(|n| n.artist.id.as_str()).collect();
+        assert_eq!(ids, ["a", "b"]);
+        assert_eq!(graph.nodes[1].depth, 1);
+        assert_eq!(graph.edges, BTreeSet::from([(0, 1)]));
+    }
+
+    #[test]
+    fn finds_clusters_and_the_bridges_between_them() {
+        let summary = two_triangles().summary();
+
+        let clusters: Vec<(&str, usize)> = summary
+            .clusters
+            .iter()
+            .map(|c| (c.name.as_str(), c.artists.len()))
+            .collect();
+        assert_eq!(clusters, [("rock", 3), ("jazz", 3)]);
+        assert_eq!(summary.clusters[0].artists[0], "A");
+
+        let bridges: Vec<(&str, Vec<String>)> = summary
+            .bridges
+            .iter()
+            .map(|b| (b.artist.as_str(), b.clusters.clone()))
+            .collect();
+        assert_eq!(
+            bridges,
+            [
+                ("D", vec!["jazz".to_string(), "rock".to_string()]),
+                ("C", vec!["rock".to_string(), "jazz".to_string()]),
+            ]
+        );
+    }
+
+    #[test]
+    fn unexplored_neighbors_leave_out_listened_artists() {
+        let mut graph = ArtistGraph::new(
+            vec![artist("a", &[]), artist("b", &[])],
+            HashSet::from(["KNOWN".to_string()]),
+        );
+        graph.expand("a", vec![artist("shared", &[]), artist("known", &[])], 100);
+        graph.expand("b", vec![artist("shared", &[]), artist("single", &[])], 100);
+
+        let summary = graph.summary();
+
+        let unexplored: Vec<(&str, usize)> = summary
+            .unexplored
+            .iter()
+            .map(|n| (n.artist.as_str(), n.related_to.len()))
+            .collect();
+        assert_eq!(unexplored, [("SHARED", 2), ("SINGLE", 1)]);
+    }
+
+    #[test]
+    fn cache_entries_expire() {
+        let mut cache = RelatedCache::default();
+        cache.insert("a".to_string(), vec![artist("b", &[])], 1_000);
+
+        assert_eq!(cache.get("a", 1_500, 600).map(|r| r.len()), Some(1));
+        assert_eq!(cache.get("a", 2_000, 600), None);
+
+        cache.prune(2_000, 600);
+        assert!(cache.artists.is_empty());
+    }
+
+    #[test]
+    fn exports_escape_artist_names() {
+        let mut graph = ArtistGraph::new(vec![artist("a", &[])], HashSet::new());
+        let mut quoted = artist("b", &[]);
+        quoted.name = "Simon & \"Garfunkel\"".to_string();
+        graph.expand("a", vec![quoted], 10);
+
+        let dot = dot::render_graph(&graph);
+        assert!(dot.contains(r#""b" [label="Simon & \"Garfunkel\"""#));
+        assert!(dot.contains(r#""a" -- "b";"#));
+
+        let graphml = graphml::render_graph(&graph);
+        assert!(graphml.contains("Simon &amp; &quot;Garfunkel&quot;"));
+        assert!(graphml.contains(r#"<edge source="a" target="b"/>"#));
+    }
+}
@@ -0,0 +1,565 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use serde::Deserialize;
+
+use crate::app::Message;
+
+/// Everything a key can be bound to in normal mode.
+///
+/// Keys that only make sense in a fixed place, such as typing into the search
+/// bar or `1`-`9` for sorting, are not part of the map.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Action {
+    ScrollDown,
+    ScrollUp,
+    PageDown,
+    PageUp,
+    ScrollTop,
+    ScrollBottom,
+    FocusNext,
+    FocusPrevious,
+    OpenDetail,
+    NextTimeRange,
+    Refresh,
+    StartSearch,
+    NextMatch,
+    PreviousMatch,
+    ClearSearch,
+    ToggleReport,
+    ToggleDiscover,
+    ToggleSeed,
+    ToggleGraph,
+    ToggleReleases,
+    NextPage,
+    PreviousPage,
+    ExportReport,
+    ExportHtml,
+    ExportCard,
+    Play,
+    PlayVisible,
+    Queue,
+    TogglePause,
+    NextTrack,
+    PreviousTrack,
+    Devices,
+    SavePlaylist,
+    Help,
+    Quit,
+}
+
+pub const ACTIONS: [Action; 35] = [
+    Action::ScrollDown,
+    Action::ScrollUp,
+    Action::PageDown,
+    Action::PageUp,
+    Action::ScrollTop,
+    Action::ScrollBottom,
+    Action::FocusNext,
+    Action::FocusPrevious,
+    Action::OpenDetail,
+    Action::NextTimeRange,
+    Action::Refresh,
+    Action::StartSearch,
+    Action::NextMatch,
+    Action::PreviousMatch,
+    Action::ClearSearch,
+    Action::ToggleReport,
+    Action::ToggleDiscover,
+    Action::ToggleSeed,
+    Action::ToggleGraph,
+    Action::ToggleReleases,
+    Action::NextPage,
+    Action::PreviousPage,
+    Action::ExportReport,
+    Action::ExportHtml,
+    Action::ExportCard,
+    Action::Play,
+    Action::PlayVisible,
+    Action::Queue,
+    Action::TogglePause,
+    Action::NextTrack,
+    Action::PreviousTrack,
+    Action::Devices,
+    Action::SavePlaylist,
+    Action::Help,
+    Action::Quit,
+];
+
+impl Action {
+    pub fn description(&self) -> &'static str {
+        match self {
+            Action::ScrollDown => "Move down",
+            Action::ScrollUp => "Move up",
+            Action::PageDown => "Page down",
+            Action::PageUp => "Page up",
+            Action::ScrollTop => "Go to top",
+            Action::ScrollBottom => "Go to bottom",
+            Action::FocusNext => "Focus next pane",
+            Action::FocusPrevious => "Focus previous pane",
+            Action::OpenDetail => "Show details",
+            Action::NextTimeRange => "Next time range",
+            Action::Refresh => "Refresh",
+            Action::StartSearch => "Search",
+            Action::NextMatch => "Next match",
+            Action::PreviousMatch => "Previous match",
+            Action::ClearSearch => "Clear search",
+            Action::ToggleReport => "Toggle wrapped report",
+            Action::ToggleDiscover => "Toggle discovery",
+            Action::ToggleSeed => "Pick selected row as a discovery seed",
+            Action::ToggleGraph => "Toggle artist graph",
+            Action::ToggleReleases => "Toggle new releases",
+            Action::NextPage => "Next report page",
+            Action::PreviousPage => "Previous report page",
+            Action::ExportReport => "Export report or artist graph",
+            Action::ExportHtml => "Export HTML stats",
+            Action::ExportCard => "Export stats card",
+            Action::Play => "Play selected track",
+            Action::PlayVisible => "Play the visible tracks",
+            Action::Queue => "Add selected track to queue",
+            Action::TogglePause => "Pause or resume",
+            Action::NextTrack => "Skip to next track",
+            Action::PreviousTrack => "Back to previous track",
+            Action::Devices => "Pick a playback device",
+            Action::SavePlaylist => "Save top tracks as a playlist",
+            Action::Help => "Toggle help",
+            Action::Quit => "Quit",
+        }
+    }
+
+    pub fn message(&self) -> Message {
+        match self {
+            Action::ScrollDown => Message::ScrollDown,
+            Action::ScrollUp => Message::ScrollUp,
+            Action::PageDown => Message::PageDown,
+            Action::PageUp => Message::PageUp,
+            Action::ScrollTop => Message::ScrollTop,
+            Action::ScrollBottom => Message::ScrollBottom,
+            Action::FocusNext => Message::FocusNext,
+            Action::FocusPrevious => Message::FocusPrevious,
+            Action::OpenDetail => Message::OpenDetail,
+            Action::NextTimeRange => Message::NextTimeRange,
+            Action::Refresh => Message::Refresh,
+            Action::StartSearch => Message::StartSearch,
+            Action::NextMatch => Message::NextMatch,
+            Action::PreviousMatch => Message::PreviousMatch,
+            Action::ClearSearch => Message::ClearSearch,
+            Action::ToggleReport => Message::ToggleReport,
+            Action::ToggleDiscover => Message::ToggleDiscover,
+            Action::ToggleSeed => Message::ToggleSeed,
+            Action::ToggleGraph => Message::ToggleGraph,
+            Action::ToggleReleases => Message::ToggleReleases,
+            Action::NextPage => Message::NextPage,
+            Action::PreviousPage => Message::PreviousPage,
+            Action::ExportReport => Message::ExportReport,
+            Action::ExportHtml => Message::ExportHtml,
+            Action::ExportCard => Message::ExportCard,
+            Action::Play => Message::PlaySelected,
+            Action::PlayVisible => Message::PlayVisible,
+            Action::Queue => Message::QueueSelected,
+            Action::TogglePause => Message::TogglePause,
+            Action::NextTrack => Message::NextTrack,
+            Action::PreviousTrack => Message::PreviousTrack,
+            Action::Devices => Message::OpenDevices,
+            Action::SavePlaylist => Message::SavePlaylist,
+            Action::Help => Message::ToggleHelp,
+            Action::Quit => Message::Quit,
+        }
+    }
+}
+
+/// A key together with the Ctrl and Alt modifiers it needs.
+///
+/// Shift is never stored: it is already part of uppercase characters and of
+/// `BackTab`, and terminals disagree about reporting it alongside them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct KeyBinding {
+    pub code: KeyCode,
+    pub modifiers: KeyModifiers,
+}
+
+impl KeyBinding {
+    pub const fn new(code: KeyCode) -> KeyBinding {
+        KeyBinding {
+            code,
+            modifiers: KeyModifiers::NONE,
+        }
+    }
+
+    pub fn matches(&self, key: &KeyEvent) -> bool {
+        let modifiers = key.modifiers & (KeyModifiers::CONTROL | KeyModifiers::ALT);
+        self.code == key.code && self.modifiers == modifiers
+    }
+
+    /// Parses bindings such as `j`, `G`, `?`, `pagedown`, `shift-tab` or
+    /// `ctrl-d`. Names are case-insensitive, single characters are not.
+    pub fn parse(text: &str) -> Result<KeyBinding, KeymapError> {
+        let invalid = || KeymapError::InvalidKey(text.to_string());
+
+        let mut modifiers = KeyModifiers::NONE;
+        let mut rest = text;
+        loop {
+            let lower = rest.to_lowercase();
+            if rest.chars().count() > 1 && lower.starts_with("ctrl-") {
+                modifiers |= KeyModifiers::CONTROL;
+                rest = &rest["ctrl-".len()..];
+            } else if rest.chars().count() > 1 && lower.starts_with("alt-") {
+                modifiers |= KeyModifiers::ALT;
+                rest = &rest["alt-".len()..];
+            } else {
+                break;
+            }
+        }
+
+        let mut chars = rest.chars();
+        let code = match (chars.next(), chars.next()) {
+            (Some(c), None) => KeyCode::Char(c),
+            (Some(_), Some(_)) => match rest.to_lowercase().as_str() {
+                "up" => KeyCode::Up,
+                "down" => KeyCode::Down,
+                "left" => KeyCode::Left,
+                "right" => KeyCode::Right,
+                "pageup" => KeyCode::PageUp,
+                "pagedown" => KeyCode::PageDown,
+                "home" => KeyCode::Home,
+                "end" => KeyCode::End,
+                "tab" => KeyCode::Tab,
+                "backtab" | "shift-tab" => KeyCode::BackTab,
+                "enter" => KeyCode::Enter,
+                "esc" => KeyCode::Esc,
+                "space" => KeyCode::Char(' '),
+                "backspace" => KeyCode::Backspace,
+                "delete" => KeyCode::Delete,
+                "insert" => KeyCode::Insert,
+                name => match name.strip_prefix('f').and_then(|n| n.parse().ok()) {
+                    Some(n @ 1..=12) => KeyCode::F(n),
+                    _ => return Err(invalid()),
+                },
+            },
+            (None, _) => return Err(invalid()),
+        };
+
+        Ok(KeyBinding { code, modifiers })
+    }
+}
+
+impl fmt::Display for KeyBinding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            write!(f, "ctrl-")?;
+        }
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            write!(f, "alt-")?;
+        }
+
+        match self.code {
+            KeyCode::Char(' ') => write!(f, "space"),
+            KeyCode::Char(c) => write!(f, "{}", c),
+            KeyCode::Up => write!(f, "↑"),
+            KeyCode::Down => write!(f, "↓"),
+            KeyCode::Left => write!(f, "←"),
+            KeyCode::Right => write!(f, "→"),
+            KeyCode::PageUp => write!(f, "pageup"),
+            KeyCode::PageDown => write!(f, "pagedown"),
+            KeyCode::Home => write!(f, "home"),
+            KeyCode::End => write!(f, "end"),
+            KeyCode::Tab => write!(f, "tab"),
+            KeyCode::BackTab => write!(f, "shift-tab"),
+            KeyCode::Enter => write!(f, "enter"),
+            KeyCode::Esc => write!(f, "esc"),
+            KeyCode::Backspace => write!(f, "backspace"),
+            KeyCode::Delete => write!(f, "delete"),
+            KeyCode::Insert => write!(f, "insert"),
+            KeyCode::F(n) => write!(f, "f{}", n),
+            code => write!(f, "{:?}", code),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum KeymapError {
+    InvalidKey(String),
+    /// The same key is bound to several actions, or to an action and one of
+    /// the fixed keys.
+    Conflict(KeyBinding, Vec<String>),
+}
+
+impl fmt::Display for KeymapError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            KeymapError::InvalidKey(key) => write!(f, "invalid key binding {:?}", key),
+            KeymapError::Conflict(key, uses) => {
+                write!(f, "key {} is bound to {}", key, uses.join(" and "))
+            }
+        }
+    }
+}
+
+impl std::error::Error for KeymapError {}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Preset {
+    /// Both the vim and the arrow-key bindings.
+    #[default]
+    Default,
+    Vim,
+    Arrows,
+}
+
+/// Keys with a fixed meaning that can't be bound to an action.
+const RESERVED: [(KeyCode, &str); 9] = [
+    (KeyCode::Char('1'), "sort by column"),
+    (KeyCode::Char('2'), "sort by column"),
+    (KeyCode::Char('3'), "sort by column"),
+    (KeyCode::Char('4'), "sort by column"),
+    (KeyCode::Char('5'), "sort by column"),
+    (KeyCode::Char('6'), "sort by column"),
+    (KeyCode::Char('7'), "sort by column"),
+    (KeyCode::Char('8'), "sort by column"),
+    (KeyCode::Char('9'), "sort by column"),
+];
+
+fn keys(codes: &[KeyCode]) -> Vec<KeyBinding> {
+    codes.iter().map(|code| KeyBinding::new(*code)).collect()
+}
+
+fn vim_keys(action: Action) -> Vec<KeyBinding> {
+    match action {
+        Action::ScrollDown => keys(&[KeyCode::Char('j')]),
+        Action::ScrollUp => keys(&[KeyCode::Char('k')]),
+        Action::PageDown => vec![KeyBinding {
+            code: KeyCode::Char('d'),
+            modifiers: KeyModifiers::CONTROL,
+        }],
+        Action::PageUp => vec![KeyBinding {
+            code: KeyCode::Char('u'),
+            modifiers: KeyModifiers::CONTROL,
+        }],
+        Action::ScrollTop => keys(&[KeyCode::Char('g')]),
+        Action::ScrollBottom => keys(&[KeyCode::Char('G')]),
+        Action::NextPage => keys(&[KeyCode::Char('l')]),
+        Action::PreviousPage => keys(&[KeyCode::Char('h')]),
+        action => common_keys(action),
+    }
+}
+
+fn arrow_keys(action: Action) -> Vec<KeyBinding> {
+    match action {
+        Action::ScrollDown => keys(&[KeyCode::Down]),
+        Action::ScrollUp => keys(&[KeyCode::Up]),
+        Action::PageDown => keys(&[KeyCode::PageDown]),
+        Action::PageUp => keys(&[KeyCode::PageUp]),
+        Action::ScrollTop => keys(&[KeyCode::Home]),
+        Action::ScrollBottom => keys(&[KeyCode::End]),
+        Action::NextPage => keys(&[KeyCode::Right]),
+        Action::PreviousPage => keys(&[KeyCode::Left]),
+        action => common_keys(action),
+    }
+}
+
+/// Bindings shared by every preset.
+fn common_keys(action: Action) -> Vec<KeyBinding> {
+    match action {
+        Action::FocusNext => keys(&[KeyCode::Tab]),
+        Action::FocusPrevious => keys(&[KeyCode::BackTab]),
+        Action::OpenDetail => keys(&[KeyCode::Enter]),
+        Action::NextTimeRange => keys(&[KeyCode::Char('t')]),
+        Action::Refresh => keys(&[KeyCode::Char('r')]),
+        Action::StartSearch => keys(&[KeyCode::Char('/')]),
+        Action::NextMatch => keys(&[KeyCode::Char('n')]),
+        Action::PreviousMatch => keys(&[KeyCode::Char('N')]),
+        Action::ClearSearch => keys(&[KeyCode::Esc]),
+        Action::ToggleReport => keys(&[KeyCode::Char('w')]),
+        Action::ToggleDiscover => keys(&[KeyCode::Char('D')]),
+        Action::ToggleSeed => keys(&[KeyCode::Char('m')]),
+        Action::ToggleGraph => keys(&[KeyCode::Char('A')]),
+        Action::ToggleReleases => keys(&[KeyCode::Char('R')]),
+        Action::ExportReport => keys(&[KeyCode::Char('e')]),
+        Action::ExportHtml => keys(&[KeyCode::Char('x')]),
+        Action::ExportCard => keys(&[KeyCode::Char('c')]),
+        Action::Play => keys(&[KeyCode::Char('p')]),
+        Action::PlayVisible => keys(&[KeyCode::Char('P')]),
+        Action::Queue => keys(&[KeyCode::Char('a')]),
+        Action::TogglePause => keys(&[KeyCode::Char(' ')]),
+        Action::NextTrack => keys(&[KeyCode::Char('>')]),
+        Action::PreviousTrack => keys(&[KeyCode::Char('<')]),
+        Action::Devices => keys(&[KeyCode::Char('d')]),
+        Action::SavePlaylist => keys(&[KeyCode::Char('s')]),
+        Action::Help => keys(&[KeyCode::Char('?')]),
+        Action::Quit => keys(&[KeyCode::Char('q')]),
+        _ => Vec::new(),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Keymap {
+    pub bindings: Vec<(Action, Vec<KeyBinding>)>,
+}
+
+impl Keymap {
+    pub fn preset(preset: Preset) -> Keymap {
+        let bindings = ACTIONS
+            .iter()
+            .map(|action| {
+                let keys = match preset {
+                    Preset::Vim => vim_keys(*action),
+                    Preset::Arrows => arrow_keys(*action),
+                    Preset::Default => {
+                        let mut keys = vim_keys(*action);
+                        for key in arrow_keys(*action) {
+                            if !keys.contains(&key) {
+                                keys.push(key);
+                            }
+                        }
+                        keys
+                    }
+                };
+                (*action, keys)
+            })
+            .collect();
+
+        Keymap { bindings }
+    }
+
+    /// Builds the keymap from a preset, replacing the keys of every action in
+    /// `overrides`, and checks the result for conflicts.
+    pub fn load(
+        preset: Preset,
+        overrides: &HashMap<Action, Vec<String>>,
+    ) -> Result<Keymap, KeymapError> {
+        let mut keymap = Keymap::preset(preset);
+
+        for (action, keys) in &mut keymap.bindings {
+            if let Some(texts) = overrides.get(action) {
+                *keys = texts
+                    .iter()
+                    .map(|text| KeyBinding::parse(text))
+                    .collect::<Result<Vec<KeyBinding>, KeymapError>>()?;
+            }
+        }
+
+        match keymap.conflicts().into_iter().next() {
+            Some(conflict) => Err(conflict),
+            None => Ok(keymap),
+        }
+    }
+
+    /// Lists every key that is bound to more than one thing.
+    pub fn conflicts(&self) -> Vec<KeymapError> {
+        let mut uses: Vec<(KeyBinding, Vec<String>)> = RESERVED
+            .iter()
+            .map(|(code, name)| (KeyBinding::new(*code), vec![name.to_string()]))
+            .collect();
+
+        for (action, keys) in &self.bindings {
+            for key in keys {
+                let name = action.description().to_lowercase();
+                match uses.iter_mut().find(|(k, _)| k == key) {
+                    Some((_, names)) => names.push(name),
+                    None => uses.push((*key, vec![name])),
+                }
+            }
+        }
+
+        uses.into_iter()
+            .filter(|(_, names)| names.len() > 1)
+            .map(|(key, names)| KeymapError::Conflict(key, names))
+            .collect()
+    }
+
+    pub fn action(&self, key: &KeyEvent) -> Option<Action> {
+        self.bindings
+            .iter()
+            .find(|(_, keys)| keys.iter().any(|binding| binding.matches(key)))
+            .map(|(action, _)| *action)
+    }
+
+    pub fn keys(&self, action: Action) -> &[KeyBinding] {
+        self.bindings
+            .iter()
+            .find(|(a, _)| *a == action)
+            .map(|(_, keys)| keys.as_slice())
+            .unwrap_or_default()
+    }
+
+    /// The keys of `action` joined for display, e.g. `j/↓`.
+    pub fn describe(&self, action: Action) -> String {
+        self.keys(action)
+            .iter()
+            .map(|key| key.to_string())
+            .collect::<Vec<String>>()
+            .join("/")
+    }
+}
+
+impl Default for Keymap {
+    fn default() -> Keymap {
+        Keymap::preset(Preset::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn overrides(action: Action, keys: &[&str]) -> HashMap<Action, Vec<String>> {
+        HashMap::from([(action, keys.iter().map(|key| key.to_string()).collect())])
+    }
+
+    #[test]
+    fn parses_modifiers_and_named_keys() {
+        assert_eq!(
+            KeyBinding::parse("ctrl-d"),
+            Ok(KeyBinding {
+                code: KeyCode::Char('d'),
+                modifiers: KeyModifiers::CONTROL,
+            })
+        );
+        assert_eq!(
+            KeyBinding::parse("shift-tab"),
+            Ok(KeyBinding::new(KeyCode::BackTab))
+        );
+        assert_eq!(KeyBinding::parse("F5"), Ok(KeyBinding::new(KeyCode::F(5))));
+        assert_eq!(
+            KeyBinding::parse("hyper-x"),
+            Err(KeymapError::InvalidKey("hyper-x".to_string()))
+        );
+    }
+
+    #[test]
+    fn rejects_overrides_that_take_a_bound_key() {
+        let result = Keymap::load(Preset::Vim, &overrides(Action::Quit, &["j"]));
+
+        assert_eq!(
+            result,
+            Err(KeymapError::Conflict(
+                KeyBinding::new(KeyCode::Char('j')),
+                vec![
+                    Action::ScrollDown.description().to_lowercase(),
+                    Action::Quit.description().to_lowercase(),
+                ],
+            ))
+        );
+    }
+
+    #[test]
+    fn rejects_overrides_that_take_a_reserved_key() {
+        let result = Keymap::load(Preset::Default, &overrides(Action::Quit, &["3"]));
+
+        assert!(matches!(
+            result,
+            Err(KeymapError::Conflict(key, uses))
+                if key == KeyBinding::new(KeyCode::Char('3')) && uses[0] == "sort by column"
+        ));
+    }
+
+    #[test]
+    fn presets_have_no_conflicts() {
+        for preset in [Preset::Default, Preset::Vim, Preset::Arrows] {
+            assert_eq!(Keymap::preset(preset).conflicts(), [], "{:?}", preset);
+        }
+    }
+}
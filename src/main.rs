@@ -1,14 +1,58 @@
+pub mod albums;
+pub mod app;
+pub mod cli;
 pub mod client;
-use std::time::Duration;
+pub mod config;
+pub mod discover;
+pub mod eras;
+pub mod event;
+pub mod export;
+#[cfg(test)]
+mod fake_spotify;
+pub mod graph;
+pub mod keymap;
+pub mod metrics;
+pub mod playback;
+pub mod playlist;
+pub mod releases;
+pub mod report;
+pub mod results;
+pub mod rolling;
+pub mod search;
+pub mod state;
+pub mod theme;
 
-use crate::client::{get_env_var, get_top_tracks, Client};
-use app2::{draw, update, Message, Model, RunningState};
-use client::{get_top_artists, get_user_display_name};
+use crate::client::{get_env_var, Client};
+use app::{draw, update, DetailRequest, InputMode, Message, Model, RunningState};
+use chrono::{Local, Utc};
+use clap::Parser;
+use cli::{Cli, Command, PlaylistArgs};
+use client::{
+    get_artist_details, get_now_playing, get_top_artists, get_top_tracks, get_track_details,
+    get_user_display_name, TIME_RANGES,
+};
+use config::Config;
+use discover::{get_recommendations, DiscoverOptions, Seed};
+use event::{spawn_event_reader, AppEvent};
+use graph::{get_graph, ArtistGraph, GraphArtist, GraphOptions, RelatedCache};
+use keymap::Action;
+use playback::{get_devices, Playback};
+use playlist::{parse_playlist_id, PlaylistOptions, PlaylistPlan, PlaylistTracks};
+use releases::{get_releases, window_start, Release, ReleaseOptions, SeenReleases};
+use results::get_range_results;
+use rolling::RollingState;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tokio::sync::mpsc::{self, UnboundedSender};
 
-pub mod app2;
-use crossterm::event::{self, Event, KeyCode};
+use color_eyre::eyre::eyre;
+use crossterm::event::{Event, KeyCode, KeyEvent};
 use dotenvy::dotenv;
-use rspotify::{model::TimeRange, AuthCodeSpotify, ClientError, Credentials};
+use rspotify::{
+    model::{AlbumId, ArtistId, TimeRange, TrackId},
+    AuthCodeSpotify, ClientError, Credentials,
+};
 
 async fn authenticate() -> Option<AuthCodeSpotify> {
     dotenv().ok();
@@ -34,28 +78,186 @@ async fn authenticate() -> Option<AuthCodeSpotify> {
     Some(client)
 }
 
-struct UserResults {
-    tracks: Vec<TopTracks>,
-    artists: Vec<TopArtists>,
+/// Fetches every time range on its own task, reporting each result on `tx`.
+fn spawn_results(client: &AuthCodeSpotify, tx: &UnboundedSender<AppEvent>, limit: u8) {
+    for time_range in TIME_RANGES {
+        let client = client.clone();
+        let tx = tx.clone();
+
+        tokio::spawn(async move {
+            let results = get_range_results(&client, time_range, limit).await;
+            let results = results.map(Box::new).map_err(|e| e.to_string());
+            let msg = Message::RangeLoaded(time_range, results);
+            let _ = tx.send(AppEvent::Message(msg));
+        });
+    }
+}
+
+fn spawn_username(client: &AuthCodeSpotify, tx: &UnboundedSender<AppEvent>) {
+    let client = client.clone();
+    let tx = tx.clone();
+
+    tokio::spawn(async move {
+        let username = get_user_display_name(&client).await;
+        let msg = Message::UsernameLoaded(username.map_err(|e| e.to_string()));
+        let _ = tx.send(AppEvent::Message(msg));
+    });
+}
+
+fn spawn_now_playing(client: &AuthCodeSpotify, tx: &UnboundedSender<AppEvent>) {
+    let client = client.clone();
+    let tx = tx.clone();
+
+    tokio::spawn(async move {
+        let now_playing = get_now_playing(&client).await;
+        let msg = Message::NowPlayingLoaded(now_playing.map_err(|e| e.to_string()));
+        let _ = tx.send(AppEvent::Message(msg));
+    });
+}
+
+fn spawn_devices(client: &AuthCodeSpotify, tx: &UnboundedSender<AppEvent>) {
+    let client = client.clone();
+    let tx = tx.clone();
+
+    tokio::spawn(async move {
+        let devices = get_devices(&client).await;
+        let msg = Message::DevicesLoaded(devices.map_err(|e| e.to_string()));
+        let _ = tx.send(AppEvent::Message(msg));
+    });
+}
+
+fn spawn_playback(
+    client: &AuthCodeSpotify,
+    tx: &UnboundedSender<AppEvent>,
+    playback: Playback,
+    device_id: Option<String>,
+) {
+    let client = client.clone();
+    let tx = tx.clone();
+
+    tokio::spawn(async move {
+        let result = playback::send(&client, &playback, device_id.as_deref()).await;
+        let msg = Message::PlaybackSent(playback, result.map_err(|e| e.to_string()));
+        let _ = tx.send(AppEvent::Message(msg));
+    });
+}
+
+fn spawn_recommendations(
+    client: &AuthCodeSpotify,
+    tx: &UnboundedSender<AppEvent>,
+    seeds: Vec<Seed>,
+    options: DiscoverOptions,
+    known: HashSet<TrackId<'static>>,
+) {
+    let client = client.clone();
+    let tx = tx.clone();
+
+    tokio::spawn(async move {
+        let recommendations = get_recommendations(&client, &seeds, &options, &known).await;
+        let msg = Message::RecommendationsLoaded(recommendations.map_err(|e| e.to_string()));
+        let _ = tx.send(AppEvent::Message(msg));
+    });
+}
+
+fn spawn_graph(
+    client: &AuthCodeSpotify,
+    tx: &UnboundedSender<AppEvent>,
+    top: Vec<GraphArtist>,
+    listened: HashSet<String>,
+    options: GraphOptions,
+) {
+    let client = client.clone();
+    let tx = tx.clone();
+
+    tokio::spawn(async move {
+        let graph = build_graph(&client, top, listened, &options, false).await;
+        let msg = Message::GraphLoaded(graph.map(Box::new).map_err(|e| e.to_string()));
+        let _ = tx.send(AppEvent::Message(msg));
+    });
+}
+
+fn spawn_releases(
+    client: &AuthCodeSpotify,
+    tx: &UnboundedSender<AppEvent>,
+    artists: Vec<ArtistId<'static>>,
+    played: HashSet<AlbumId<'static>>,
+    options: ReleaseOptions,
+) {
+    let client = client.clone();
+    let tx = tx.clone();
+
+    tokio::spawn(async move {
+        let releases = match releases::default_seen_path() {
+            Some(path) => check_releases(&client, &artists, &played, &options, &path).await,
+            None => Err(eyre!("no data directory for the state file")),
+        };
+        let msg = Message::ReleasesLoaded(releases.map_err(|e| e.to_string()));
+        let _ = tx.send(AppEvent::Message(msg));
+    });
+}
+
+fn spawn_playlist_plan(
+    client: &AuthCodeSpotify,
+    tx: &UnboundedSender<AppEvent>,
+    tracks: PlaylistTracks,
+    options: PlaylistOptions,
+) {
+    let client = client.clone();
+    let tx = tx.clone();
+
+    tokio::spawn(async move {
+        let today = Local::now().date_naive();
+        let plan = playlist::plan(&client, tracks, &options, None, today).await;
+        let msg = Message::PlaylistPlanned(plan.map(Box::new).map_err(|e| e.to_string()));
+        let _ = tx.send(AppEvent::Message(msg));
+    });
 }
 
-async fn get_results(client: &AuthCodeSpotify) -> Result<UserResults, ClientError> {
-    let result: UserResults;
-    let short_term = get_top_tracks(&client, TimeRange::ShortTerm, 50).await?;
-    let medium_term = get_top_tracks(&client, TimeRange::MediumTerm, 50).await?;
-    let long_term = get_top_tracks(&client, TimeRange::LongTerm, 50).await?;
+fn spawn_playlist_save(
+    client: &AuthCodeSpotify,
+    tx: &UnboundedSender<AppEvent>,
+    plan: PlaylistPlan,
+) {
+    let client = client.clone();
+    let tx = tx.clone();
 
-    result.tracks.append(short_term)
+    tokio::spawn(async move {
+        let result = playlist::write(&client, &plan).await;
+        let msg = Message::PlaylistSaved(
+            result
+                .map(|_| plan.done_message())
+                .map_err(|e| e.to_string()),
+        );
+        let _ = tx.send(AppEvent::Message(msg));
+    });
+}
+
+fn spawn_detail(client: &AuthCodeSpotify, tx: &UnboundedSender<AppEvent>, request: DetailRequest) {
+    let client = client.clone();
+    let tx = tx.clone();
+
+    tokio::spawn(async move {
+        let msg = fetch_detail(&client, request).await;
+        let _ = tx.send(AppEvent::Message(msg));
+    });
 }
 
 #[tokio::main]
 async fn main() -> color_eyre::Result<()> {
     dotenv().ok();
     color_eyre::install()?;
+    let cli = Cli::parse();
+    let config = Config::load(cli.config.as_deref())?;
 
     let mut model = Model {
         time_range: TimeRange::ShortTerm,
         limit: 10,
+        keymap: config.keymap()?,
+        theme: config.theme()?,
+        playlist_options: config.playlist.clone(),
+        discover_options: config.discover.clone(),
+        graph_options: config.graph.clone(),
+        release_options: config.releases.clone(),
         ..Default::default()
     };
 
@@ -66,46 +268,396 @@ async fn main() -> color_eyre::Result<()> {
         return Ok(());
     };
 
-    let top_tracks = get_top_tracks(&client, model.time_range, model.limit as u8).await?;
-    model.top_tracks = top_tracks;
+    match cli.command {
+        Some(Command::Card { range, png, out }) => {
+            for time_range in TIME_RANGES {
+                model.set_range_results(
+                    get_range_results(&client, time_range, model.limit as u8).await?,
+                );
+            }
+            model.username = get_user_display_name(&client).await?;
 
-    let top_artists = get_top_artists(&client, model.time_range, model.limit as u8).await?;
-    model.top_artists = top_artists;
+            for path in export::write_card(&model, range.into(), png, &out)? {
+                println!("Wrote {}", path.display());
+            }
+            return Ok(());
+        }
+        Some(Command::Playlist(args)) => {
+            return write_playlist(&client, &args, &config.playlist).await;
+        }
+        Some(Command::Rolling {
+            every,
+            state,
+            dry_run,
+        }) => {
+            return sync_rolling(&client, &config, state, every, dry_run).await;
+        }
+        Some(Command::Graph {
+            depth,
+            limit,
+            refresh,
+            out,
+        }) => {
+            let options = GraphOptions {
+                depth: depth.map_or(config.graph.depth, |depth| depth as usize),
+                ..config.graph.clone()
+            };
+            return map_artists(&client, &options, limit, refresh, &out).await;
+        }
+        Some(Command::Releases { days, limit, state }) => {
+            let options = ReleaseOptions {
+                days: days.unwrap_or(config.releases.days),
+            };
+            return list_releases(&client, &options, limit, state).await;
+        }
+        None => {}
+    }
 
-    println!("Hello {}!", model.username);
     tui::install_panic_hook();
-
     let mut terminal = tui::init_terminal()?;
 
+    let (tx, mut rx) = mpsc::unbounded_channel();
+    spawn_event_reader(tx.clone());
+    spawn_username(&client, &tx);
+
+    let mut current_msg = Some(Message::Refresh);
+
     while model.running_state != RunningState::Done {
+        while let Some(msg) = current_msg {
+            current_msg = match update(&mut model, msg) {
+                Some(command) => run_command(&client, &tx, &model, command),
+                None => None,
+            };
+        }
+
         terminal.draw(|f| draw(&mut model, f))?;
 
-        let current_msg = handle_event(&model)?;
+        current_msg = match rx.recv().await {
+            Some(AppEvent::Terminal(Event::Key(key))) => handle_key(&model, key),
+            Some(AppEvent::Terminal(Event::Mouse(mouse))) => Some(Message::Mouse(mouse)),
+            Some(AppEvent::Terminal(Event::Resize(_, _))) => Some(Message::Resize),
+            Some(AppEvent::Terminal(Event::FocusGained)) => Some(Message::FocusGained),
+            Some(AppEvent::Terminal(Event::FocusLost)) => Some(Message::FocusLost),
+            Some(AppEvent::Terminal(_)) => None,
+            Some(AppEvent::Tick) => Some(Message::Tick),
+            Some(AppEvent::Message(msg)) => Some(msg),
+            None => Some(Message::Quit),
+        };
+    }
+
+    tui::restore_terminal()?;
+    Ok(())
+}
+
+/// The `playlist` subcommand.
+async fn write_playlist(
+    client: &AuthCodeSpotify,
+    args: &PlaylistArgs,
+    config: &PlaylistOptions,
+) -> color_eyre::Result<()> {
+    let options = args.options(config);
+    options.validate()?;
+    let target = args
+        .playlist
+        .as_deref()
+        .map(parse_playlist_id)
+        .transpose()?;
+
+    let top_tracks = get_top_tracks(client, args.range.into(), args.limit).await?;
+    let plan = playlist::plan(
+        client,
+        PlaylistTracks::from_top_tracks(&top_tracks),
+        &options,
+        target,
+        Local::now().date_naive(),
+    )
+    .await?;
+
+    if args.dry_run {
+        for line in plan.preview() {
+            println!("{}", line);
+        }
+        return Ok(());
+    }
+    playlist::write(client, &plan).await?;
+    println!("{}", plan.done_message());
+    Ok(())
+}
+
+/// The `rolling` subcommand.
+async fn sync_rolling(
+    client: &AuthCodeSpotify,
+    config: &Config,
+    state_path: Option<PathBuf>,
+    every: Option<u64>,
+    dry_run: bool,
+) -> color_eyre::Result<()> {
+    let Some(state_path) = state_path.or_else(rolling::default_state_path) else {
+        return Err(eyre!("no data directory for the state file, pass --state"));
+    };
+    let playlists = if config.rolling.is_empty() {
+        rolling::default_playlists()
+    } else {
+        config.rolling.clone()
+    };
+
+    loop {
+        let mut state = RollingState::load(&state_path)?;
+        let today = Local::now().date_naive();
+        let result = rolling::sync(client, &playlists, &mut state, today, dry_run).await;
+        if !dry_run {
+            // Saved even after a failure, so playlists made before it are
+            // still known on the next run.
+            state.save(&state_path)?;
+        }
 
-        while current_msg.is_some() {
-            current_msg = update(&mut model, current_msg.unwrap());
+        match (result, every) {
+            (Ok(report), _) => {
+                for line in report {
+                    println!("{}", line);
+                }
+            }
+            (Err(e), None) => return Err(e.into()),
+            (Err(e), Some(_)) => eprintln!("Sync failed: {}", e),
         }
+
+        let Some(hours) = every else {
+            return Ok(());
+        };
+        tokio::time::sleep(Duration::from_secs(hours * 60 * 60)).await;
     }
+}
 
+/// The `graph` subcommand.
+async fn map_artists(
+    client: &AuthCodeSpotify,
+    options: &GraphOptions,
+    limit: u8,
+    refresh: bool,
+    out: &Path,
+) -> color_eyre::Result<()> {
+    let mut top = Vec::new();
+    let mut listened = HashSet::new();
+    for time_range in TIME_RANGES {
+        let top_artists = get_top_artists(client, time_range, limit).await?;
+        top.extend(top_artists.artists.iter().map(GraphArtist::from));
+        let top_tracks = get_top_tracks(client, time_range, limit).await?;
+        listened.extend(
+            top_tracks
+                .tracks
+                .into_iter()
+                .flat_map(|track| track.artists),
+        );
+    }
+
+    let graph = build_graph(client, top, listened, options, refresh).await?;
+    for line in graph.summary().lines() {
+        println!("{}", line);
+    }
+    println!();
+    for path in export::write_graph(&graph, out)? {
+        println!("Wrote {}", path.display());
+    }
     Ok(())
 }
 
-fn handle_event(_: &Model) -> color_eyre::Result<Option<Message>> {
-    if event::poll(Duration::from_millis(250))? {
-        if let Event::Key(key) = event::read()? {
-            if key.kind == event::KeyEventKind::Press {
-                return Ok(handle_key(key));
+/// The `releases` subcommand.
+async fn list_releases(
+    client: &AuthCodeSpotify,
+    options: &ReleaseOptions,
+    limit: u8,
+    state_path: Option<PathBuf>,
+) -> color_eyre::Result<()> {
+    let Some(state_path) = state_path.or_else(releases::default_seen_path) else {
+        return Err(eyre!("no data directory for the state file, pass --state"));
+    };
+
+    let mut artists: Vec<ArtistId<'static>> = Vec::new();
+    let mut played = HashSet::new();
+    for time_range in TIME_RANGES {
+        for artist in get_top_artists(client, time_range, limit).await?.artists {
+            if !artists.contains(&artist.id) {
+                artists.push(artist.id);
             }
         }
+        let top_tracks = get_top_tracks(client, time_range, limit).await?;
+        played.extend(
+            top_tracks
+                .tracks
+                .into_iter()
+                .filter_map(|track| track.album_id),
+        );
+    }
+
+    let releases = check_releases(client, &artists, &played, options, &state_path).await?;
+    if releases.is_empty() {
+        println!(
+            "Nothing you haven't heard in the last {} days",
+            options.days
+        );
+    }
+    for release in releases {
+        println!("{}", release.line());
+    }
+    Ok(())
+}
+
+/// Fetches the new releases of `artists` and records them as seen, so that
+/// the next check only marks newer ones as new.
+async fn check_releases(
+    client: &AuthCodeSpotify,
+    artists: &[ArtistId<'static>],
+    played: &HashSet<AlbumId<'static>>,
+    options: &ReleaseOptions,
+    state_path: &Path,
+) -> color_eyre::Result<Vec<Release>> {
+    let mut seen = SeenReleases::load(state_path)?;
+    let today = Local::now().date_naive();
+
+    let releases = get_releases(client, artists, played, options, &seen, today).await?;
+    seen.mark(&releases, window_start(options, today));
+    seen.save(state_path)?;
+    Ok(releases)
+}
+
+/// Builds the artist graph, reusing the related artists cached by earlier
+/// builds unless `refresh` is set.
+async fn build_graph(
+    client: &AuthCodeSpotify,
+    top: Vec<GraphArtist>,
+    listened: HashSet<String>,
+    options: &GraphOptions,
+    refresh: bool,
+) -> Result<ArtistGraph, ClientError> {
+    let path = graph::default_cache_path();
+    let mut cache = match &path {
+        Some(path) if !refresh => RelatedCache::load(path),
+        _ => RelatedCache::default(),
+    };
+
+    let graph = get_graph(
+        client,
+        top,
+        listened,
+        options,
+        &mut cache,
+        Utc::now().timestamp(),
+    )
+    .await;
+    if let Some(path) = path {
+        // Saved even after a failure, keeping what was fetched before it.
+        // The cache only saves requests, so failing to write it is no error.
+        let _ = cache.save(&path);
     }
-    Ok(None)
+    graph
 }
 
-fn handle_key(key: event::KeyEvent) -> Option<Message> {
-    match key.code {
-        KeyCode::Char('j') => Some(Message::ScrollDown),
-        KeyCode::Char('k') => Some(Message::ScrollUp),
-        KeyCode::Char('q') => Some(Message::Quit),
+/// Carries out a side effect requested by `update`. Background work reports
+/// back on `tx`; anything done in place returns its result right away.
+fn run_command(
+    client: &AuthCodeSpotify,
+    tx: &UnboundedSender<AppEvent>,
+    model: &Model,
+    command: app::Command,
+) -> Option<Message> {
+    match command {
+        app::Command::LoadRanges => spawn_results(client, tx, model.limit as u8),
+        app::Command::FetchDetail(request) => spawn_detail(client, tx, request),
+        app::Command::FetchRecommendations(seeds) => spawn_recommendations(
+            client,
+            tx,
+            seeds,
+            model.discover_options.clone(),
+            model.known_track_ids(),
+        ),
+        app::Command::BuildGraph(top, listened) => {
+            spawn_graph(client, tx, top, listened, model.graph_options.clone())
+        }
+        app::Command::FetchReleases(artists, played) => {
+            spawn_releases(client, tx, artists, played, model.release_options.clone())
+        }
+        app::Command::FetchNowPlaying => spawn_now_playing(client, tx),
+        app::Command::FetchDevices => spawn_devices(client, tx),
+        app::Command::Playback(playback) => {
+            let device_id = model.device.as_ref().map(|device| device.id.clone());
+            spawn_playback(client, tx, playback, device_id)
+        }
+        app::Command::PlanPlaylist(tracks, options) => {
+            spawn_playlist_plan(client, tx, tracks, options)
+        }
+        app::Command::SavePlaylist(plan) => spawn_playlist_save(client, tx, *plan),
+        app::Command::Export(export) => {
+            let result = export::write(model, export, Path::new("."));
+            return Some(Message::Exported(result.map_err(|e| e.to_string())));
+        }
+    }
+    None
+}
+
+async fn fetch_detail(client: &AuthCodeSpotify, request: DetailRequest) -> Message {
+    match request {
+        DetailRequest::Track(id) => {
+            let details = get_track_details(client, id.clone()).await;
+            Message::TrackDetailsLoaded(id, details.map_err(|e| e.to_string()))
+        }
+        DetailRequest::Artist(id) => {
+            let details = get_artist_details(client, id.clone()).await;
+            Message::ArtistDetailsLoaded(id, details.map_err(|e| e.to_string()))
+        }
+    }
+}
+
+fn handle_key(model: &Model, key: KeyEvent) -> Option<Message> {
+    if model.input_mode == InputMode::Search {
+        return match key.code {
+            KeyCode::Esc => Some(Message::ClearSearch),
+            KeyCode::Enter => Some(Message::ConfirmSearch),
+            _ => Some(Message::SearchInput(key)),
+        };
+    }
+
+    if model.show_help {
+        return match (key.code, model.keymap.action(&key)) {
+            (KeyCode::Esc, _) | (_, Some(Action::Help | Action::Quit)) => Some(Message::ToggleHelp),
+            _ => None,
+        };
+    }
+
+    if model.devices.is_some() {
+        return match (key.code, model.keymap.action(&key)) {
+            (KeyCode::Esc, _) | (_, Some(Action::Devices | Action::Quit)) => {
+                Some(Message::CloseDevices)
+            }
+            (KeyCode::Enter, _) => Some(Message::SelectDevice),
+            (_, Some(Action::ScrollDown)) => Some(Message::NextDevice),
+            (_, Some(Action::ScrollUp)) => Some(Message::PreviousDevice),
+            _ => None,
+        };
+    }
+
+    if model.playlist_plan.is_some() {
+        return match (key.code, model.keymap.action(&key)) {
+            (KeyCode::Esc, _) | (_, Some(Action::SavePlaylist | Action::Quit)) => {
+                Some(Message::CancelPlaylist)
+            }
+            (KeyCode::Enter, _) => Some(Message::ConfirmPlaylist),
+            _ => None,
+        };
+    }
+
+    if model.detail.is_some() {
+        return match (key.code, model.keymap.action(&key)) {
+            (KeyCode::Esc | KeyCode::Enter, _) | (_, Some(Action::Quit)) => {
+                Some(Message::CloseDetail)
+            }
+            _ => None,
+        };
+    }
+
+    match (key.code, model.keymap.action(&key)) {
+        (_, Some(Action::ClearSearch)) if model.search.value().is_empty() => None,
+        (_, Some(action)) => Some(action.message()),
+        (KeyCode::Char(c @ '1'..='9'), None) => Some(Message::SortBy(c as usize - '1' as usize)),
         _ => None,
     }
 }
@@ -114,6 +666,9 @@ mod tui {
     use ratatui::{
         backend::{Backend, CrosstermBackend},
         crossterm::{
+            event::{
+                DisableFocusChange, DisableMouseCapture, EnableFocusChange, EnableMouseCapture,
+            },
             terminal::{
                 disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen,
             },
@@ -126,11 +681,16 @@ mod tui {
     pub fn init_terminal() -> color_eyre::Result<Terminal<impl Backend>> {
         enable_raw_mode()?;
         stdout().execute(EnterAlternateScreen)?;
+        stdout().execute(EnableMouseCapture)?;
+        // Lets polling pause while the terminal is in the background.
+        stdout().execute(EnableFocusChange)?;
         let terminal = Terminal::new(CrosstermBackend::new(stdout()))?;
         Ok(terminal)
     }
 
     pub fn restore_terminal() -> color_eyre::Result<()> {
+        stdout().execute(DisableFocusChange)?;
+        stdout().execute(DisableMouseCapture)?;
         stdout().execute(LeaveAlternateScreen)?;
         disable_raw_mode()?;
         Ok(())
@@ -139,6 +699,8 @@ mod tui {
     pub fn install_panic_hook() {
         let original_hook = panic::take_hook();
         panic::set_hook(Box::new(move |panic_info| {
+            stdout().execute(DisableFocusChange).unwrap();
+            stdout().execute(DisableMouseCapture).unwrap();
             stdout().execute(LeaveAlternateScreen).unwrap();
             disable_raw_mode().unwrap();
             original_hook(panic_info);
@@ -0,0 +1,189 @@
+use rspotify::model::TimeRange;
+
+use crate::client::{TopArtists, TopTracks};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ListeningMetrics {
+    pub time_range: TimeRange,
+    /// Normalized Shannon entropy of the primary artists of the top tracks.
+    pub artist_entropy: Option<f64>,
+    /// Gini coefficient of the primary artists of the top tracks.
+    pub artist_gini: Option<f64>,
+    /// Normalized Shannon entropy of the genres of the top artists.
+    pub genre_entropy: Option<f64>,
+    /// Gini coefficient of the genres of the top artists.
+    pub genre_gini: Option<f64>,
+    /// Share of the top tracks held by the three most frequent artists.
+    pub artist_concentration: Option<f64>,
+    /// `100 - average popularity` of the top tracks.
+    pub track_obscurity: Option<f64>,
+    /// `100 - average popularity` of the top artists.
+    pub artist_obscurity: Option<f64>,
+}
+
+fn counts<'a, I>(items: I) -> Vec<usize>
+where
+    I: IntoIterator<Item = &'a str>,
+{
+    let mut counts: Vec<(&str, usize)> = Vec::new();
+
+    for item in items {
+        match counts.iter_mut().find(|(key, _)| *key == item) {
+            Some((_, count)) => *count += 1,
+            None => counts.push((item, 1)),
+        }
+    }
+
+    counts.into_iter().map(|(_, count)| count).collect()
+}
+
+/// Shannon entropy of the counts, divided by its maximum so that 0 means a
+/// single item and 1 means every item appears equally often.
+pub fn normalized_entropy(counts: &[usize]) -> Option<f64> {
+    let total: usize = counts.iter().sum();
+    if total == 0 {
+        return None;
+    }
+    if counts.len() == 1 {
+        return Some(0.0);
+    }
+
+    let entropy: f64 = counts
+        .iter()
+        .filter(|count| **count > 0)
+        .map(|count| {
+            let p = *count as f64 / total as f64;
+            -p * p.ln()
+        })
+        .sum();
+
+    Some(entropy / (counts.len() as f64).ln())
+}
+
+/// Gini coefficient of the counts, where 0 is perfect equality and values
+/// approaching 1 mean a few items dominate.
+pub fn gini(counts: &[usize]) -> Option<f64> {
+    let total: usize = counts.iter().sum();
+    if total == 0 {
+        return None;
+    }
+
+    let mut sorted = counts.to_vec();
+    sorted.sort();
+
+    let n = sorted.len() as f64;
+    let weighted: f64 = sorted
+        .iter()
+        .enumerate()
+        .map(|(i, count)| (i + 1) as f64 * *count as f64)
+        .sum();
+
+    Some(2.0 * weighted / (n * total as f64) - (n + 1.0) / n)
+}
+
+fn top_share(counts: &[usize], top: usize) -> Option<f64> {
+    let total: usize = counts.iter().sum();
+    if total == 0 {
+        return None;
+    }
+
+    let mut sorted = counts.to_vec();
+    sorted.sort_by(|a, b| b.cmp(a));
+    let held: usize = sorted.iter().take(top).sum();
+
+    Some(held as f64 / total as f64)
+}
+
+fn obscurity<I>(popularities: I) -> Option<f64>
+where
+    I: IntoIterator<Item = u32>,
+{
+    let popularities: Vec<u32> = popularities.into_iter().collect();
+    if popularities.is_empty() {
+        return None;
+    }
+
+    let total: u32 = popularities.iter().sum();
+    Some(100.0 - total as f64 / popularities.len() as f64)
+}
+
+/// Computes diversity, concentration and obscurity for a single time range.
+///
+/// Artist figures only count the first credited artist of each track so that
+/// the shares of all artists add up to the number of tracks.
+pub fn get_listening_metrics(top_tracks: &TopTracks, top_artists: &TopArtists) -> ListeningMetrics {
+    let artist_counts = counts(
+        top_tracks
+            .tracks
+            .iter()
+            .filter_map(|track| track.artists.first())
+            .map(|artist| artist.as_str()),
+    );
+    let genre_counts = counts(
+        top_artists
+            .artists
+            .iter()
+            .flat_map(|artist| artist.genres.iter())
+            .map(|genre| genre.as_str()),
+    );
+
+    ListeningMetrics {
+        time_range: top_tracks.time_range,
+        artist_entropy: normalized_entropy(&artist_counts),
+        artist_gini: gini(&artist_counts),
+        genre_entropy: normalized_entropy(&genre_counts),
+        genre_gini: gini(&genre_counts),
+        artist_concentration: top_share(&artist_counts, 3),
+        track_obscurity: obscurity(top_tracks.tracks.iter().map(|track| track.popularity)),
+        artist_obscurity: obscurity(top_artists.artists.iter().map(|artist| artist.popularity)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: Option<f64>, expected: f64) {
+        let actual = actual.expect("a value");
+        assert!(
+            (actual - expected).abs() < 1e-9,
+            "{} != {}",
+            actual,
+            expected
+        );
+    }
+
+    #[test]
+    fn empty_and_all_zero_counts_have_no_figures() {
+        for counts in [&[][..], &[0, 0, 0][..]] {
+            assert_eq!(normalized_entropy(counts), None);
+            assert_eq!(gini(counts), None);
+        }
+        assert_eq!(obscurity([]), None);
+    }
+
+    #[test]
+    fn a_single_artist_has_no_diversity() {
+        assert_close(normalized_entropy(&[7]), 0.0);
+        assert_close(gini(&[7]), 0.0);
+    }
+
+    #[test]
+    fn a_uniform_distribution_is_fully_diverse_and_equal() {
+        assert_close(normalized_entropy(&[3, 3, 3, 3]), 1.0);
+        assert_close(gini(&[3, 3, 3, 3]), 0.0);
+    }
+
+    #[test]
+    fn a_dominant_artist_lowers_entropy_and_raises_gini() {
+        let skewed = [9, 1, 0];
+        assert!(normalized_entropy(&skewed).unwrap() < 0.5);
+        assert_close(gini(&skewed), 0.6);
+    }
+
+    #[test]
+    fn obscurity_is_the_complement_of_the_average_popularity() {
+        assert_close(obscurity([100, 50]), 25.0);
+        assert_close(obscurity([0]), 100.0);
+    }
+}
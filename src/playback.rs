@@ -0,0 +1,209 @@
+use rspotify::clients::OAuthClient;
+use rspotify::model::{PlayableId, TrackId};
+use rspotify::{AuthCodeSpotify, ClientError};
+
+/// A Spotify Connect device that playback can be sent to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlaybackDevice {
+    pub id: String,
+    pub name: String,
+    /// E.g. `Computer` or `Speaker`.
+    pub kind: String,
+    pub is_active: bool,
+}
+
+/// A request to the player of the user's active, or chosen, device.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Playback {
+    /// Plays the tracks in order, replacing whatever was playing.
+    Play(Vec<TrackId<'static>>),
+    Queue(TrackId<'static>),
+    Pause,
+    Resume,
+    Next,
+    Previous,
+    /// Moves playback to the device and keeps it playing.
+    Transfer(PlaybackDevice),
+}
+
+impl Playback {
+    /// What happened once the request succeeded.
+    pub fn done_message(&self) -> String {
+        match self {
+            Playback::Play(ids) if ids.len() == 1 => "Playing track".to_string(),
+            Playback::Play(ids) => format!("Playing {} tracks", ids.len()),
+            Playback::Queue(_) => "Added to queue".to_string(),
+            Playback::Pause => "Paused".to_string(),
+            Playback::Resume => "Resumed".to_string(),
+            Playback::Next => "Skipped to next track".to_string(),
+            Playback::Previous => "Back to previous track".to_string(),
+            Playback::Transfer(device) => format!("Playing on {}", device.name),
+        }
+    }
+}
+
+/// The devices playback can be sent to. Restricted devices, which can't be
+/// controlled through the Web API, have no id and are left out.
+pub async fn get_devices(client: &AuthCodeSpotify) -> Result<Vec<PlaybackDevice>, ClientError> {
+    let devices = client.device().await?;
+
+    let result = devices
+        .into_iter()
+        .filter_map(|device| {
+            Some(PlaybackDevice {
+                id: device.id?,
+                name: device.name,
+                kind: format!("{:?}", device._type),
+                is_active: device.is_active,
+            })
+        })
+        .collect();
+
+    Ok(result)
+}
+
+/// Sends `playback` to `device_id`, or to the active device when it is
+/// `None`.
+pub async fn send(
+    client: &AuthCodeSpotify,
+    playback: &Playback,
+    device_id: Option<&str>,
+) -> Result<(), ClientError> {
+    match playback {
+        Playback::Play(ids) => {
+            let uris: Vec<PlayableId> = ids.iter().cloned().map(PlayableId::Track).collect();
+            client
+                .start_uris_playback(uris, device_id, None, None)
+                .await
+        }
+        Playback::Queue(id) => {
+            client
+                .add_item_to_queue(PlayableId::Track(id.clone()), device_id)
+                .await
+        }
+        Playback::Pause => client.pause_playback(device_id).await,
+        Playback::Resume => client.resume_playback(device_id, None).await,
+        Playback::Next => client.next_track(device_id).await,
+        Playback::Previous => client.previous_track(device_id).await,
+        Playback::Transfer(device) => client.transfer_playback(&device.id, Some(true)).await,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fake_spotify::{FakeSpotify, Route};
+
+    const DEVICES: &str = r#"{"devices": [
+        {"id": "laptop", "is_active": true, "is_private_session": false, "is_restricted": false,
+         "name": "Laptop", "type": "Computer", "volume_percent": 60},
+        {"id": null, "is_active": false, "is_private_session": false, "is_restricted": true,
+         "name": "Car", "type": "Automobile", "volume_percent": null},
+        {"id": "kitchen", "is_active": false, "is_private_session": false, "is_restricted": false,
+         "name": "Kitchen", "type": "Speaker", "volume_percent": 30}
+    ]}"#;
+
+    const ROUTES: &[Route] = &[("GET", "me/player/devices", DEVICES)];
+
+    fn track(id: &str) -> TrackId<'static> {
+        TrackId::from_id(id.to_string()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn lists_controllable_devices() {
+        let spotify = FakeSpotify::start(ROUTES);
+
+        let devices = get_devices(&spotify.client()).await.unwrap();
+
+        let names: Vec<&str> = devices.iter().map(|d| d.name.as_str()).collect();
+        assert_eq!(names, ["Laptop", "Kitchen"]);
+        assert!(devices[0].is_active);
+        assert_eq!(devices[1].kind, "Speaker");
+    }
+
+    #[tokio::test]
+    async fn plays_tracks_in_order_on_the_chosen_device() {
+        let spotify = FakeSpotify::start(ROUTES);
+        let playback = Playback::Play(vec![
+            track("4uLU6hMCjMI75M1A2tKUQC"),
+            track("7ouMYWpwJ422jRcDASZB7P"),
+        ]);
+
+        send(&spotify.client(), &playback, Some("kitchen"))
+            .await
+            .unwrap();
+
+        let requests = spotify.requests();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].method, "PUT");
+        assert_eq!(requests[0].path, "me/player/play?device_id=kitchen");
+        let first = requests[0]
+            .body
+            .find("spotify:track:4uLU6hMCjMI75M1A2tKUQC")
+            .unwrap();
+        let second = requests[0]
+            .body
+            .find("spotify:track:7ouMYWpwJ422jRcDASZB7P")
+            .unwrap();
+        assert!(first < second);
+    }
+
+    #[tokio::test]
+    async fn queues_a_track() {
+        let spotify = FakeSpotify::start(ROUTES);
+        let playback = Playback::Queue(track("4uLU6hMCjMI75M1A2tKUQC"));
+
+        send(&spotify.client(), &playback, None).await.unwrap();
+
+        let requests = spotify.requests();
+        assert_eq!(requests[0].method, "POST");
+        assert!(requests[0].path.starts_with("me/player/queue?"));
+        assert!(requests[0].path.contains("4uLU6hMCjMI75M1A2tKUQC"));
+    }
+
+    #[tokio::test]
+    async fn controls_the_player() {
+        let spotify = FakeSpotify::start(ROUTES);
+        let client = spotify.client();
+
+        for playback in [
+            Playback::Pause,
+            Playback::Resume,
+            Playback::Next,
+            Playback::Previous,
+        ] {
+            send(&client, &playback, None).await.unwrap();
+        }
+
+        let requests: Vec<(String, String)> = spotify
+            .requests()
+            .into_iter()
+            .map(|r| (r.method, r.path))
+            .collect();
+        assert_eq!(
+            requests,
+            [
+                ("PUT".to_string(), "me/player/pause".to_string()),
+                ("PUT".to_string(), "me/player/play".to_string()),
+                ("POST".to_string(), "me/player/next".to_string()),
+                ("POST".to_string(), "me/player/previous".to_string()),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn transfers_playback_to_a_device() {
+        let spotify = FakeSpotify::start(ROUTES);
+        let client = spotify.client();
+        let devices = get_devices(&client).await.unwrap();
+
+        send(&client, &Playback::Transfer(devices[1].clone()), None)
+            .await
+            .unwrap();
+
+        let transfer = &spotify.requests()[1];
+        assert_eq!(transfer.method, "PUT");
+        assert_eq!(transfer.path, "me/player");
+        assert!(transfer.body.contains("kitchen"));
+    }
+}
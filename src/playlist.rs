@@ -0,0 +1,539 @@
+use std::fmt;
+
+use chrono::NaiveDate;
+use futures::{pin_mut, TryStreamExt};
+use rspotify::clients::{BaseClient, OAuthClient};
+use rspotify::model::{IdError, PlayableId, PlaylistId, TimeRange, TrackId, UserId};
+use rspotify::{AuthCodeSpotify, ClientError};
+use serde::Deserialize;
+
+use crate::client::{show_time_range, TopTracks};
+
+/// The name template used when neither the command line nor the config set
+/// one.
+pub const DEFAULT_NAME: &str = "Top {count} – {range} – {date}";
+
+/// What happens to the tracks already in an existing playlist.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WriteMode {
+    /// They are swapped for the top tracks.
+    #[default]
+    Replace,
+    /// They are kept and the top tracks are added after them.
+    Append,
+}
+
+/// How top tracks are written into playlists, read from the `[playlist]`
+/// section of the config.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PlaylistOptions {
+    /// Name template, see [`render_name`].
+    pub name: String,
+    /// Only applies to playlists that get created.
+    pub public: bool,
+    pub mode: WriteMode,
+}
+
+impl Default for PlaylistOptions {
+    fn default() -> Self {
+        PlaylistOptions {
+            name: DEFAULT_NAME.to_string(),
+            public: false,
+            mode: WriteMode::default(),
+        }
+    }
+}
+
+impl PlaylistOptions {
+    /// Checks the name template without waiting for a playlist to be written.
+    pub fn validate(&self) -> Result<(), TemplateError> {
+        let date = NaiveDate::from_ymd_opt(2000, 1, 1).unwrap_or_default();
+        render_name(&self.name, &TimeRange::ShortTerm, 0, date).map(|_| ())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TemplateError {
+    UnknownPlaceholder(String),
+    Unclosed,
+}
+
+impl fmt::Display for TemplateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TemplateError::UnknownPlaceholder(name) => write!(
+                f,
+                "unknown placeholder {{{}}}, expected one of {{range}}, {{count}}, {{date}}, {{month}} or {{year}}",
+                name
+            ),
+            TemplateError::Unclosed => write!(f, "a {{ is never closed"),
+        }
+    }
+}
+
+impl std::error::Error for TemplateError {}
+
+/// Fills in the placeholders of a playlist name template:
+///
+/// - `{range}`: the time range, e.g. `Short Term`
+/// - `{count}`: how many tracks the playlist gets
+/// - `{date}`: `date` as `2024-05-31`
+/// - `{month}`: `date` as `May 2024`
+/// - `{year}`: `date` as `2024`
+pub fn render_name(
+    template: &str,
+    time_range: &TimeRange,
+    count: usize,
+    date: NaiveDate,
+) -> Result<String, TemplateError> {
+    let mut name = String::new();
+    let mut rest = template;
+
+    while let Some(start) = rest.find('{') {
+        name.push_str(&rest[..start]);
+        let Some(end) = rest[start..].find('}').map(|end| start + end) else {
+            return Err(TemplateError::Unclosed);
+        };
+        match &rest[start + 1..end] {
+            "range" => name.push_str(&show_time_range(time_range)),
+            "count" => name.push_str(&count.to_string()),
+            "date" => name.push_str(&date.format("%Y-%m-%d").to_string()),
+            "month" => name.push_str(&date.format("%B %Y").to_string()),
+            "year" => name.push_str(&date.format("%Y").to_string()),
+            placeholder => return Err(TemplateError::UnknownPlaceholder(placeholder.to_string())),
+        }
+        rest = &rest[end + 1..];
+    }
+    name.push_str(rest);
+
+    Ok(name)
+}
+
+#[derive(Debug)]
+pub enum PlaylistError {
+    Template(TemplateError),
+    Client(ClientError),
+    /// None of the tracks can be added to a playlist.
+    NoTracks,
+}
+
+impl fmt::Display for PlaylistError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PlaylistError::Template(e) => write!(f, "invalid playlist name: {}", e),
+            PlaylistError::Client(e) => write!(f, "{}", e),
+            PlaylistError::NoTracks => write!(f, "there are no tracks to add"),
+        }
+    }
+}
+
+impl std::error::Error for PlaylistError {}
+
+impl From<TemplateError> for PlaylistError {
+    fn from(e: TemplateError) -> Self {
+        PlaylistError::Template(e)
+    }
+}
+
+impl From<ClientError> for PlaylistError {
+    fn from(e: ClientError) -> Self {
+        PlaylistError::Client(e)
+    }
+}
+
+/// Reads a playlist id, `spotify:playlist:` URI or `open.spotify.com` link.
+pub fn parse_playlist_id(text: &str) -> Result<PlaylistId<'static>, IdError> {
+    let text = text.trim();
+    let id = match text.split_once("open.spotify.com/playlist/") {
+        Some((_, rest)) => rest.split(['?', '/']).next().unwrap_or_default(),
+        None => text,
+    };
+    PlaylistId::from_id_or_uri(id).map(|id| id.into_static())
+}
+
+/// A playlist of the user's that the top tracks are written into.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExistingPlaylist {
+    pub id: PlaylistId<'static>,
+    /// How many tracks it has right now.
+    pub tracks: u32,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlannedTrack {
+    pub id: TrackId<'static>,
+    pub name: String,
+    pub artists: Vec<String>,
+}
+
+/// Everything [`write`] is going to do, worked out without changing
+/// anything so that it can be previewed first.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlaylistPlan {
+    pub user_id: UserId<'static>,
+    pub name: String,
+    /// `None` when the playlist is going to be created.
+    pub existing: Option<ExistingPlaylist>,
+    pub public: bool,
+    pub mode: WriteMode,
+    pub time_range: TimeRange,
+    pub tracks: Vec<PlannedTrack>,
+    /// Top tracks without an id, such as local files, which can't be added.
+    pub skipped: usize,
+}
+
+fn count_tracks(count: usize) -> String {
+    match count {
+        1 => "1 track".to_string(),
+        count => format!("{} tracks", count),
+    }
+}
+
+impl PlaylistPlan {
+    /// What writing the plan is going to do.
+    pub fn summary(&self) -> String {
+        let tracks = count_tracks(self.tracks.len());
+        match (&self.existing, self.mode) {
+            (None, _) => format!(
+                "Create {} playlist \"{}\" with {}",
+                if self.public { "public" } else { "private" },
+                self.name,
+                tracks
+            ),
+            (Some(existing), WriteMode::Replace) => format!(
+                "Replace the {} in \"{}\" with {}",
+                count_tracks(existing.tracks as usize),
+                self.name,
+                tracks
+            ),
+            (Some(existing), WriteMode::Append) => format!(
+                "Add {} to \"{}\" after its {}",
+                tracks,
+                self.name,
+                count_tracks(existing.tracks as usize)
+            ),
+        }
+    }
+
+    /// What happened once the plan was written.
+    pub fn done_message(&self) -> String {
+        let tracks = count_tracks(self.tracks.len());
+        match (&self.existing, self.mode) {
+            (None, _) => format!("Created \"{}\" with {}", self.name, tracks),
+            (Some(_), WriteMode::Replace) => format!("Replaced \"{}\" with {}", self.name, tracks),
+            (Some(_), WriteMode::Append) => format!("Added {} to \"{}\"", tracks, self.name),
+        }
+    }
+
+    /// The summary followed by the numbered tracks, for a dry run.
+    pub fn preview(&self) -> Vec<String> {
+        let mut lines = vec![self.summary()];
+        if self.skipped > 0 {
+            lines.push(format!(
+                "Skipping {} without an id",
+                count_tracks(self.skipped)
+            ));
+        }
+        lines.push(String::new());
+        lines.extend(self.tracks.iter().enumerate().map(|(i, track)| {
+            format!(
+                "{:>2}. {} - {}",
+                i + 1,
+                track.name,
+                track.artists.join(", ")
+            )
+        }));
+        lines
+    }
+}
+
+/// Tracks on their way into a playlist.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlaylistTracks {
+    /// The range the tracks were picked from, for `{range}` in names.
+    pub time_range: TimeRange,
+    pub tracks: Vec<PlannedTrack>,
+    /// Tracks without an id, such as local files, which can't be added.
+    pub skipped: usize,
+}
+
+impl PlaylistTracks {
+    pub fn from_top_tracks(top_tracks: &TopTracks) -> PlaylistTracks {
+        let tracks: Vec<PlannedTrack> = top_tracks
+            .tracks
+            .iter()
+            .filter_map(|track| {
+                Some(PlannedTrack {
+                    id: track.id.clone()?,
+                    name: track.track_name.clone(),
+                    artists: track.artists.clone(),
+                })
+            })
+            .collect();
+
+        PlaylistTracks {
+            time_range: top_tracks.time_range,
+            skipped: top_tracks.tracks.len() - tracks.len(),
+            tracks,
+        }
+    }
+}
+
+/// Looks for a playlist owned by the user with exactly `name`.
+async fn find_owned_playlist(
+    client: &AuthCodeSpotify,
+    user_id: &UserId<'static>,
+    name: &str,
+) -> Result<Option<ExistingPlaylist>, ClientError> {
+    let stream = client.current_user_playlists();
+    pin_mut!(stream);
+
+    while let Some(playlist) = stream.try_next().await? {
+        if playlist.owner.id == *user_id && playlist.name == name {
+            return Ok(Some(ExistingPlaylist {
+                id: playlist.id,
+                tracks: playlist.tracks.total,
+            }));
+        }
+    }
+
+    Ok(None)
+}
+
+/// Works out how `tracks` would be written: into `playlist` when given,
+/// otherwise into the user's playlist named after `options.name`, which is
+/// created when there is none.
+pub async fn plan(
+    client: &AuthCodeSpotify,
+    tracks: PlaylistTracks,
+    options: &PlaylistOptions,
+    playlist: Option<PlaylistId<'static>>,
+    date: NaiveDate,
+) -> Result<PlaylistPlan, PlaylistError> {
+    if tracks.tracks.is_empty() {
+        return Err(PlaylistError::NoTracks);
+    }
+    let user_id = client.me().await?.id;
+    let (name, existing) = match playlist {
+        Some(id) => {
+            let playlist = client.playlist(id, None, None).await?;
+            let existing = ExistingPlaylist {
+                id: playlist.id,
+                tracks: playlist.tracks.total,
+            };
+            (playlist.name, Some(existing))
+        }
+        None => {
+            let count = tracks.tracks.len();
+            let name = render_name(&options.name, &tracks.time_range, count, date)?;
+            let existing = find_owned_playlist(client, &user_id, &name).await?;
+            (name, existing)
+        }
+    };
+
+    Ok(PlaylistPlan {
+        user_id,
+        name,
+        existing,
+        public: options.public,
+        mode: options.mode,
+        time_range: tracks.time_range,
+        tracks: tracks.tracks,
+        skipped: tracks.skipped,
+    })
+}
+
+/// Carries out `plan`, returning the id of the playlist written into.
+pub async fn write(
+    client: &AuthCodeSpotify,
+    plan: &PlaylistPlan,
+) -> Result<PlaylistId<'static>, ClientError> {
+    let items: Vec<PlayableId<'static>> = plan
+        .tracks
+        .iter()
+        .map(|track| PlayableId::Track(track.id.clone()))
+        .collect();
+
+    let id = match &plan.existing {
+        Some(existing) => existing.id.clone(),
+        None => {
+            let description = format!(
+                "Top tracks of the {} range, made with spotifystats",
+                show_time_range(&plan.time_range).to_lowercase()
+            );
+            client
+                .user_playlist_create(
+                    plan.user_id.as_ref(),
+                    &plan.name,
+                    Some(plan.public),
+                    Some(false),
+                    Some(&description),
+                )
+                .await?
+                .id
+        }
+    };
+
+    // A playlist can only be given 100 items at a time, so a replacement
+    // writes the first hundred and adds the rest after them.
+    let mut chunks = items.chunks(100);
+    if let (Some(_), WriteMode::Replace) = (&plan.existing, plan.mode) {
+        let first = chunks.next().unwrap_or_default();
+        client
+            .playlist_replace_items(id.as_ref(), first.to_vec())
+            .await?;
+    }
+    for chunk in chunks {
+        client
+            .playlist_add_items(id.as_ref(), chunk.to_vec(), None)
+            .await?;
+    }
+
+    Ok(id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fake_spotify::{FakeSpotify, Route};
+
+    fn date() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 5, 31).unwrap()
+    }
+
+    fn sample_plan(existing: Option<ExistingPlaylist>, mode: WriteMode) -> PlaylistPlan {
+        let track = |id: &str, name: &str| PlannedTrack {
+            id: TrackId::from_id(id.to_string()).unwrap(),
+            name: name.to_string(),
+            artists: vec!["Artist".to_string()],
+        };
+        PlaylistPlan {
+            user_id: UserId::from_id("user".to_string()).unwrap(),
+            name: "Top 2".to_string(),
+            existing,
+            public: false,
+            mode,
+            time_range: TimeRange::ShortTerm,
+            tracks: vec![
+                track("4uLU6hMCjMI75M1A2tKUQC", "First"),
+                track("7ouMYWpwJ422jRcDASZB7P", "Second"),
+            ],
+            skipped: 1,
+        }
+    }
+
+    fn existing() -> Option<ExistingPlaylist> {
+        Some(ExistingPlaylist {
+            id: PlaylistId::from_id("37i9dQZF1DXcBWIGoYBM5M".to_string()).unwrap(),
+            tracks: 1,
+        })
+    }
+
+    #[test]
+    fn renders_every_placeholder() {
+        let name = render_name(
+            "Top {count} – {range} – {date} ({month}, {year})",
+            &TimeRange::MediumTerm,
+            50,
+            date(),
+        );
+
+        assert_eq!(
+            name.unwrap(),
+            "Top 50 – Medium Term – 2026-05-31 (May 2026, 2026)"
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_and_unclosed_placeholders() {
+        let render = |template| render_name(template, &TimeRange::ShortTerm, 1, date());
+
+        assert_eq!(
+            render("Top {artist}"),
+            Err(TemplateError::UnknownPlaceholder("artist".to_string()))
+        );
+        assert_eq!(render("Top {count"), Err(TemplateError::Unclosed));
+        assert!(PlaylistOptions::default().validate().is_ok());
+    }
+
+    #[test]
+    fn parses_playlist_ids_uris_and_links() {
+        let expected = existing().unwrap().id;
+
+        for text in [
+            "37i9dQZF1DXcBWIGoYBM5M",
+            "spotify:playlist:37i9dQZF1DXcBWIGoYBM5M",
+            "https://open.spotify.com/playlist/37i9dQZF1DXcBWIGoYBM5M?si=abc123",
+        ] {
+            assert_eq!(parse_playlist_id(text), Ok(expected.clone()));
+        }
+        assert!(
+            parse_playlist_id("https://open.spotify.com/track/4uLU6hMCjMI75M1A2tKUQC").is_err()
+        );
+    }
+
+    #[test]
+    fn previews_what_will_happen() {
+        let created = sample_plan(None, WriteMode::Replace);
+        assert_eq!(
+            created.preview(),
+            [
+                "Create private playlist \"Top 2\" with 2 tracks",
+                "Skipping 1 track without an id",
+                "",
+                " 1. First - Artist",
+                " 2. Second - Artist",
+            ]
+        );
+
+        let replaced = sample_plan(existing(), WriteMode::Replace);
+        assert_eq!(
+            replaced.summary(),
+            "Replace the 1 track in \"Top 2\" with 2 tracks"
+        );
+        assert_eq!(replaced.done_message(), "Replaced \"Top 2\" with 2 tracks");
+
+        let appended = sample_plan(existing(), WriteMode::Append);
+        assert_eq!(
+            appended.summary(),
+            "Add 2 tracks to \"Top 2\" after its 1 track"
+        );
+    }
+
+    #[tokio::test]
+    async fn writes_long_playlists_a_hundred_tracks_at_a_time() {
+        const ROUTES: &[Route] = &[(
+            "POST",
+            "playlists/37i9dQZF1DXcBWIGoYBM5M/tracks",
+            r#"{"snapshot_id": "abc"}"#,
+        )];
+        let spotify = FakeSpotify::start(ROUTES);
+        let mut plan = sample_plan(existing(), WriteMode::Replace);
+        plan.tracks = (0..150)
+            .map(|i| PlannedTrack {
+                id: TrackId::from_id(format!("{i:022}")).unwrap(),
+                name: format!("Track {i}"),
+                artists: vec!["Artist".to_string()],
+            })
+            .collect();
+
+        write(&spotify.client(), &plan).await.unwrap();
+
+        let requests = spotify.requests();
+        let uris = |body: &str| body.matches("spotify:track:").count();
+        assert_eq!(requests.len(), 2);
+        assert_eq!(requests[0].method, "PUT");
+        assert_eq!(requests[0].path, "playlists/37i9dQZF1DXcBWIGoYBM5M/tracks");
+        assert_eq!(uris(&requests[0].body), 100);
+        assert!(requests[0]
+            .body
+            .contains(&format!("spotify:track:{:022}", 0)));
+        assert_eq!(requests[1].method, "POST");
+        assert_eq!(uris(&requests[1].body), 50);
+        assert!(requests[1]
+            .body
+            .contains(&format!("spotify:track:{:022}", 100)));
+    }
+}
@@ -0,0 +1,326 @@
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use chrono::{Days, NaiveDate};
+use rspotify::clients::{BaseClient, OAuthClient};
+use rspotify::model::{AlbumId, AlbumType, ArtistId, Id, SimplifiedAlbum};
+use rspotify::{AuthCodeSpotify, ClientError};
+use serde::{Deserialize, Serialize};
+
+use crate::client::get_artists;
+use crate::state::{self, StateError};
+
+/// Settings of the new releases view, read from the `[releases]` section of
+/// the config.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ReleaseOptions {
+    /// How many days back a release counts as new.
+    pub days: u32,
+}
+
+impl Default for ReleaseOptions {
+    fn default() -> Self {
+        ReleaseOptions { days: 30 }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Release {
+    pub id: AlbumId<'static>,
+    pub name: String,
+    pub artists: Vec<String>,
+    /// `album` or `single`, as Spotify groups them.
+    pub kind: String,
+    pub release_date: NaiveDate,
+    /// Whether no earlier check showed it.
+    pub new: bool,
+}
+
+impl Release {
+    /// The release as a line of the `releases` subcommand, starred when new.
+    pub fn line(&self) -> String {
+        format!(
+            "{} {}  {:<6}  {} – {}",
+            if self.new { "*" } else { " " },
+            self.release_date,
+            self.kind,
+            self.name,
+            self.artists.join(", ")
+        )
+    }
+}
+
+/// Parses a release date given to the day, month or year, taking the first
+/// day of the month or year when it is less precise.
+pub fn parse_release_date(date: &str) -> Option<NaiveDate> {
+    let mut parts = date.split('-').map(|part| part.parse::<u32>().ok());
+    let year = parts.next()??;
+    let month = parts.next().unwrap_or(Some(1))?;
+    let day = parts.next().unwrap_or(Some(1))?;
+
+    NaiveDate::from_ymd_opt(i32::try_from(year).ok()?, month, day)
+}
+
+fn to_release(album: SimplifiedAlbum, group: AlbumType) -> Option<Release> {
+    let kind = match group {
+        AlbumType::Single => "single",
+        _ => "album",
+    };
+
+    Some(Release {
+        id: album.id?,
+        release_date: parse_release_date(album.release_date.as_deref()?)?,
+        name: album.name,
+        artists: get_artists(album.artists),
+        kind: kind.to_string(),
+        new: true,
+    })
+}
+
+/// The releases already shown, with their release dates so that they can be
+/// forgotten once they are too old to show again.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SeenReleases {
+    /// Release dates as `YYYY-MM-DD`, by album id.
+    #[serde(default)]
+    pub seen: BTreeMap<String, String>,
+}
+
+/// `$XDG_DATA_HOME/spotifystats/releases.toml` or the platform equivalent.
+pub fn default_seen_path() -> Option<PathBuf> {
+    dirs::data_dir().map(|dir| dir.join("spotifystats").join("releases.toml"))
+}
+
+impl SeenReleases {
+    /// Reads the seen releases at `path`, none before the first check.
+    pub fn load(path: &Path) -> Result<SeenReleases, StateError> {
+        state::load(path)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), StateError> {
+        state::save(self, path)
+    }
+
+    pub fn contains(&self, id: &AlbumId) -> bool {
+        self.seen.contains_key(id.id())
+    }
+
+    /// Records `releases` as seen and forgets those released before `since`,
+    /// which can't show up again.
+    pub fn mark(&mut self, releases: &[Release], since: NaiveDate) {
+        for release in releases {
+            self.seen.insert(
+                release.id.id().to_string(),
+                release.release_date.to_string(),
+            );
+        }
+        self.seen
+            .retain(|_, date| parse_release_date(date).is_some_and(|date| date >= since));
+    }
+}
+
+/// Keeps the releases from `since` on that aren't in `skipped` nor among
+/// the albums in `played`, once each and newest first, and marks those not
+/// in `seen` as new.
+pub fn select(
+    releases: Vec<Release>,
+    since: NaiveDate,
+    skipped: &HashSet<AlbumId<'static>>,
+    played: &HashSet<AlbumId<'static>>,
+    seen: &SeenReleases,
+) -> Vec<Release> {
+    let mut ids = HashSet::new();
+    // The same release can be listed under several ids, e.g. once per
+    // market.
+    let mut titles = HashSet::new();
+
+    let mut selected: Vec<Release> = releases
+        .into_iter()
+        .filter(|release| release.release_date >= since)
+        .filter(|release| !skipped.contains(&release.id) && !played.contains(&release.id))
+        .filter(|release| {
+            let title = (release.name.to_lowercase(), release.artists.clone());
+            ids.insert(release.id.clone()) && titles.insert(title)
+        })
+        .map(|release| Release {
+            new: !seen.contains(&release.id),
+            ..release
+        })
+        .collect();
+
+    selected.sort_by(|a, b| {
+        b.release_date
+            .cmp(&a.release_date)
+            .then(a.name.cmp(&b.name))
+    });
+    selected
+}
+
+/// The first day of the window of `options` as of `today`.
+pub fn window_start(options: &ReleaseOptions, today: NaiveDate) -> NaiveDate {
+    today
+        .checked_sub_days(Days::new(u64::from(options.days)))
+        .unwrap_or(NaiveDate::MIN)
+}
+
+/// The albums and singles of `artists` released in the window of `options`
+/// that the user hasn't saved or recently played, nor has among the albums
+/// in `played`.
+pub async fn get_releases(
+    client: &AuthCodeSpotify,
+    artists: &[ArtistId<'static>],
+    played: &HashSet<AlbumId<'static>>,
+    options: &ReleaseOptions,
+    seen: &SeenReleases,
+    today: NaiveDate,
+) -> Result<Vec<Release>, ClientError> {
+    let since = window_start(options, today);
+
+    let mut releases = Vec::new();
+    for artist in artists {
+        // Asked for a group at a time, since a page of both could be all
+        // albums and miss the latest singles.
+        for group in [AlbumType::Album, AlbumType::Single] {
+            let page = client
+                .artist_albums_manual(artist.clone(), [group], None, Some(50), None)
+                .await?;
+            releases.extend(
+                page.items
+                    .into_iter()
+                    .filter_map(|album| to_release(album, group))
+                    .filter(|release| release.release_date >= since),
+            );
+        }
+    }
+
+    let recent = client.current_user_recently_played(Some(50), None).await?;
+    let mut skipped: HashSet<AlbumId<'static>> = recent
+        .items
+        .into_iter()
+        .filter_map(|history| history.track.album.id)
+        .collect();
+
+    let mut seen_ids = HashSet::new();
+    let candidates: Vec<AlbumId<'static>> = releases
+        .iter()
+        .map(|release| release.id.clone())
+        .filter(|id| !skipped.contains(id) && seen_ids.insert(id.clone()))
+        .collect();
+    // The library can only be checked 20 albums at a time.
+    for chunk in candidates.chunks(20) {
+        let saved = client
+            .current_user_saved_albums_contains(chunk.to_vec())
+            .await?;
+        skipped.extend(
+            chunk
+                .iter()
+                .zip(saved)
+                .filter(|(_, saved)| *saved)
+                .map(|(id, _)| id.clone()),
+        );
+    }
+
+    Ok(select(releases, since, &skipped, played, seen))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(text: &str) -> NaiveDate {
+        parse_release_date(text).unwrap()
+    }
+
+    fn release(id: &str, name: &str, released: &str) -> Release {
+        Release {
+            id: AlbumId::from_id(id.to_string()).unwrap(),
+            name: name.to_string(),
+            artists: vec!["Björk".to_string()],
+            kind: "album".to_string(),
+            release_date: date(released),
+            new: true,
+        }
+    }
+
+    fn names(releases: &[Release]) -> Vec<&str> {
+        releases.iter().map(|r| r.name.as_str()).collect()
+    }
+
+    #[test]
+    fn parses_release_dates_of_any_precision() {
+        assert_eq!(
+            parse_release_date("2026-10-02"),
+            NaiveDate::from_ymd_opt(2026, 10, 2)
+        );
+        assert_eq!(
+            parse_release_date("2026-10"),
+            NaiveDate::from_ymd_opt(2026, 10, 1)
+        );
+        assert_eq!(
+            parse_release_date("2026"),
+            NaiveDate::from_ymd_opt(2026, 1, 1)
+        );
+        assert_eq!(parse_release_date("soon"), None);
+    }
+
+    #[test]
+    fn selects_recent_unheard_releases_newest_first() {
+        let releases = vec![
+            release("old", "Vespertine", "2001-08-27"),
+            release("saved", "Fossora", "2026-10-01"),
+            release("played", "Utopia", "2026-10-05"),
+            release("single", "Atopos", "2026-10-03"),
+            release("album", "Biophilia", "2026-10-10"),
+            release("market", "biophilia", "2026-10-10"),
+        ];
+        let skipped = HashSet::from([AlbumId::from_id("saved".to_string()).unwrap()]);
+        let played = HashSet::from([AlbumId::from_id("played".to_string()).unwrap()]);
+
+        let selected = select(
+            releases,
+            date("2026-09-18"),
+            &skipped,
+            &played,
+            &SeenReleases::default(),
+        );
+
+        assert_eq!(names(&selected), ["Biophilia", "Atopos"]);
+    }
+
+    #[test]
+    fn only_unseen_releases_are_new() {
+        let since = date("2026-09-18");
+        let mut seen = SeenReleases::default();
+        seen.mark(&[release("seen", "Atopos", "2026-10-03")], since);
+
+        let releases = vec![
+            release("seen", "Atopos", "2026-10-03"),
+            release("unseen", "Biophilia", "2026-10-10"),
+        ];
+        let selected = select(releases, since, &HashSet::new(), &HashSet::new(), &seen);
+
+        let new: Vec<bool> = selected.iter().map(|r| r.new).collect();
+        assert_eq!(new, [true, false]);
+    }
+
+    #[test]
+    fn seen_releases_are_forgotten_once_out_of_the_window() {
+        let path =
+            std::env::temp_dir().join(format!("spotifystats-releases-{}.toml", std::process::id()));
+        let mut seen = SeenReleases::load(&path).unwrap();
+        seen.mark(
+            &[release("old", "Vespertine", "2026-08-01")],
+            date("2026-07-01"),
+        );
+        seen.mark(
+            &[release("new", "Atopos", "2026-10-03")],
+            date("2026-09-18"),
+        );
+        seen.save(&path).unwrap();
+
+        let seen = SeenReleases::load(&path).unwrap();
+        assert_eq!(seen.seen.keys().collect::<Vec<_>>(), ["new"]);
+        std::fs::remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,409 @@
+use rspotify::model::TimeRange;
+
+use crate::albums::{TopAlbum, TopAlbums};
+use crate::client::{show_time_range, TopArtist, TopArtists, TopTrack, TopTracks};
+use crate::eras::ReleaseEras;
+use crate::metrics::ListeningMetrics;
+
+const SECTION_SIZE: usize = 5;
+/// Width of the longest bar of the decade histogram, in characters.
+const DECADE_BAR_WIDTH: usize = 20;
+
+#[derive(Debug, Clone)]
+pub struct Climber {
+    pub track_name: String,
+    pub artists: Vec<String>,
+    pub previous_index: usize,
+    pub index: usize,
+}
+
+/// A year-in-review style summary of a single time range.
+#[derive(Debug, Clone)]
+pub struct Report {
+    pub time_range: TimeRange,
+    pub top_tracks: Vec<TopTrack>,
+    pub top_artists: Vec<TopArtist>,
+    pub top_albums: Vec<TopAlbum>,
+    pub top_genres: Vec<(String, usize)>,
+    /// Artists in this range that don't appear in the next longer range.
+    pub new_discoveries: Vec<String>,
+    pub diversity: Option<f64>,
+    /// Tracks that moved up the most compared to the next longer range.
+    pub biggest_climbers: Vec<Climber>,
+    pub median_year: Option<i32>,
+    pub nostalgia_index: Option<f64>,
+    /// Number of top tracks released in each decade, oldest first.
+    pub decades: Vec<(i32, usize)>,
+}
+
+/// A titled block of the report, shared by the slideshow and the exporters.
+#[derive(Debug, Clone)]
+pub struct ReportSection {
+    pub title: String,
+    pub lines: Vec<String>,
+    pub ordered: bool,
+}
+
+impl Report {
+    pub fn title(&self) -> String {
+        format!("Your {} Wrapped", show_time_range(&self.time_range))
+    }
+
+    pub fn sections(&self) -> Vec<ReportSection> {
+        let or_empty = |lines: Vec<String>, empty: &str| {
+            if lines.is_empty() {
+                vec![empty.to_string()]
+            } else {
+                lines
+            }
+        };
+
+        let diversity = match self.diversity {
+            Some(diversity) => format!("Artist diversity score: {:.0}/100", diversity * 100.0),
+            None => "Artist diversity score: not enough data".to_string(),
+        };
+        let median_year = match self.median_year {
+            Some(year) => format!("Median release year: {}", year),
+            None => "Median release year: unknown".to_string(),
+        };
+        let nostalgia = match self.nostalgia_index {
+            Some(age) => format!(
+                "Nostalgia index: your music is {:.1} years old on average",
+                age
+            ),
+            None => "Nostalgia index: unknown".to_string(),
+        };
+
+        let max_decade = self
+            .decades
+            .iter()
+            .map(|(_, count)| *count)
+            .max()
+            .unwrap_or(1);
+        let decades = self
+            .decades
+            .iter()
+            .map(|(decade, count)| {
+                let width = (count * DECADE_BAR_WIDTH).div_ceil(max_decade);
+                format!("{}s {} {}", decade, "█".repeat(width), count)
+            })
+            .collect();
+
+        vec![
+            ReportSection {
+                title: "Top Tracks".to_string(),
+                lines: or_empty(
+                    self.top_tracks
+                        .iter()
+                        .map(|t| format!("{} by {}", t.track_name, t.artists.join(", ")))
+                        .collect(),
+                    "No tracks",
+                ),
+                ordered: true,
+            },
+            ReportSection {
+                title: "Top Artists".to_string(),
+                lines: or_empty(
+                    self.top_artists
+                        .iter()
+                        .map(|a| a.artist_name.clone())
+                        .collect(),
+                    "No artists",
+                ),
+                ordered: true,
+            },
+            ReportSection {
+                title: "Top Albums".to_string(),
+                lines: or_empty(
+                    self.top_albums
+                        .iter()
+                        .map(|a| format!("{} by {}", a.album_name, a.artists.join(", ")))
+                        .collect(),
+                    "No albums",
+                ),
+                ordered: true,
+            },
+            ReportSection {
+                title: "Top Genres".to_string(),
+                lines: or_empty(
+                    self.top_genres
+                        .iter()
+                        .map(|(genre, count)| format!("{} ({} artists)", genre, count))
+                        .collect(),
+                    "No genres",
+                ),
+                ordered: true,
+            },
+            ReportSection {
+                title: "Listening".to_string(),
+                lines: vec![diversity, median_year, nostalgia],
+                ordered: false,
+            },
+            ReportSection {
+                title: "Release Decades".to_string(),
+                lines: or_empty(decades, "No release dates"),
+                ordered: false,
+            },
+            ReportSection {
+                title: "New Discoveries".to_string(),
+                lines: or_empty(self.new_discoveries.clone(), "No new artists"),
+                ordered: false,
+            },
+            ReportSection {
+                title: "Biggest Climbers".to_string(),
+                lines: or_empty(
+                    self.biggest_climbers
+                        .iter()
+                        .map(|c| {
+                            format!(
+                                "{} by {} (#{} -> #{})",
+                                c.track_name,
+                                c.artists.join(", "),
+                                c.previous_index,
+                                c.index
+                            )
+                        })
+                        .collect(),
+                    "No climbers",
+                ),
+                ordered: false,
+            },
+            ReportSection {
+                title: "About This Report".to_string(),
+                lines: vec![
+                    format!(
+                        "Built from your Spotify top lists of the {} range",
+                        show_time_range(&self.time_range).to_lowercase()
+                    ),
+                    "Spotify only keeps top lists for its three ranges and no listening \
+                     history, so there are no reports for a chosen year or of minutes \
+                     listened"
+                        .to_string(),
+                ],
+                ordered: false,
+            },
+        ]
+    }
+}
+
+/// The range this one is compared against for discoveries and climbers.
+fn previous_time_range(time_range: TimeRange) -> Option<TimeRange> {
+    match time_range {
+        TimeRange::ShortTerm => Some(TimeRange::MediumTerm),
+        TimeRange::MediumTerm => Some(TimeRange::LongTerm),
+        TimeRange::LongTerm => None,
+    }
+}
+
+/// Counts how many of the top artists belong to each genre, most common first.
+pub fn get_top_genres(top_artists: &TopArtists, limit: usize) -> Vec<(String, usize)> {
+    let mut genres: Vec<(String, usize)> = Vec::new();
+
+    for genre in top_artists.artists.iter().flat_map(|a| a.genres.iter()) {
+        match genres.iter_mut().find(|(name, _)| name == genre) {
+            Some((_, count)) => *count += 1,
+            None => genres.push((genre.clone(), 1)),
+        }
+    }
+
+    // Stable sort keeps genres of higher ranked artists first on ties.
+    genres.sort_by(|(_, a), (_, b)| b.cmp(a));
+    genres.truncate(limit);
+    genres
+}
+
+fn new_discoveries(top_artists: &TopArtists, previous: Option<&TopArtists>) -> Vec<String> {
+    let Some(previous) = previous else {
+        return Vec::new();
+    };
+
+    top_artists
+        .artists
+        .iter()
+        .filter(|artist| {
+            !previous
+                .artists
+                .iter()
+                .any(|known| known.artist_name == artist.artist_name)
+        })
+        .take(SECTION_SIZE)
+        .map(|artist| artist.artist_name.clone())
+        .collect()
+}
+
+fn biggest_climbers(top_tracks: &TopTracks, previous: Option<&TopTracks>) -> Vec<Climber> {
+    let Some(previous) = previous else {
+        return Vec::new();
+    };
+
+    let mut climbers: Vec<Climber> = top_tracks
+        .tracks
+        .iter()
+        .filter_map(|track| {
+            let before = previous
+                .tracks
+                .iter()
+                .find(|old| old.track_name == track.track_name && old.artists == track.artists)?;
+
+            (before.index > track.index).then(|| Climber {
+                track_name: track.track_name.clone(),
+                artists: track.artists.clone(),
+                previous_index: before.index,
+                index: track.index,
+            })
+        })
+        .collect();
+
+    climbers.sort_by_key(|c| std::cmp::Reverse(c.previous_index - c.index));
+    climbers.truncate(SECTION_SIZE);
+    climbers
+}
+
+fn find_range<T, F>(items: &[T], time_range: TimeRange, range_of: F) -> Option<&T>
+where
+    F: Fn(&T) -> TimeRange,
+{
+    items.iter().find(|item| range_of(item) == time_range)
+}
+
+/// Assembles the report for `time_range` from the per-range results.
+pub fn get_report(
+    time_range: TimeRange,
+    tracks: &[TopTracks],
+    artists: &[TopArtists],
+    albums: &[TopAlbums],
+    metrics: &[ListeningMetrics],
+    eras: &[ReleaseEras],
+) -> Report {
+    let previous = previous_time_range(time_range);
+
+    let top_tracks = find_range(tracks, time_range, |t| t.time_range);
+    let top_artists = find_range(artists, time_range, |a| a.time_range);
+    let top_albums = find_range(albums, time_range, |a| a.time_range);
+    let metrics = find_range(metrics, time_range, |m| m.time_range);
+    let eras = find_range(eras, time_range, |e| e.time_range);
+
+    let previous_tracks = previous.and_then(|range| find_range(tracks, range, |t| t.time_range));
+    let previous_artists = previous.and_then(|range| find_range(artists, range, |a| a.time_range));
+
+    Report {
+        time_range,
+        top_tracks: top_tracks
+            .map(|t| t.tracks.iter().take(SECTION_SIZE).cloned().collect())
+            .unwrap_or_default(),
+        top_artists: top_artists
+            .map(|a| a.artists.iter().take(SECTION_SIZE).cloned().collect())
+            .unwrap_or_default(),
+        top_albums: top_albums
+            .map(|a| a.albums.iter().take(SECTION_SIZE).cloned().collect())
+            .unwrap_or_default(),
+        top_genres: top_artists
+            .map(|a| get_top_genres(a, SECTION_SIZE))
+            .unwrap_or_default(),
+        new_discoveries: top_artists
+            .map(|a| new_discoveries(a, previous_artists))
+            .unwrap_or_default(),
+        diversity: metrics.and_then(|m| m.artist_entropy),
+        biggest_climbers: top_tracks
+            .map(|t| biggest_climbers(t, previous_tracks))
+            .unwrap_or_default(),
+        median_year: eras.and_then(|e| e.median_year),
+        nostalgia_index: eras.and_then(|e| e.nostalgia_index),
+        decades: eras.map(|e| e.decades.clone()).unwrap_or_default(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rspotify::model::ArtistId;
+
+    use super::*;
+
+    fn tracks(time_range: TimeRange, names: &[&str]) -> TopTracks {
+        let tracks = names
+            .iter()
+            .enumerate()
+            .map(|(i, name)| TopTrack {
+                index: i + 1,
+                id: None,
+                track_name: name.to_string(),
+                duration: "3:00".to_string(),
+                duration_ms: 180_000,
+                artists: vec!["Artist".to_string()],
+                album_id: None,
+                album_name: "Album".to_string(),
+                album_artists: vec!["Artist".to_string()],
+                release_date: None,
+                popularity: 50,
+            })
+            .collect();
+        TopTracks { time_range, tracks }
+    }
+
+    fn artists(time_range: TimeRange, names: &[&str]) -> TopArtists {
+        let artists = names
+            .iter()
+            .enumerate()
+            .map(|(i, name)| TopArtist {
+                index: i + 1,
+                id: ArtistId::from_id("4Z8W4fKeB5YxbusRsdQVPb".to_string()).unwrap(),
+                artist_name: name.to_string(),
+                genres: Vec::new(),
+                popularity: 50,
+                followers: 1000,
+            })
+            .collect();
+        TopArtists {
+            time_range,
+            artists,
+        }
+    }
+
+    fn climbs(climbers: &[Climber]) -> Vec<(&str, usize, usize)> {
+        climbers
+            .iter()
+            .map(|c| (c.track_name.as_str(), c.previous_index, c.index))
+            .collect()
+    }
+
+    #[test]
+    fn climbers_are_ordered_by_how_far_they_moved_up() {
+        let current = tracks(TimeRange::ShortTerm, &["A", "B", "C", "D", "E"]);
+        let previous = tracks(
+            TimeRange::MediumTerm,
+            &["X", "Y", "Z", "A", "W", "B", "C", "E"],
+        );
+
+        let climbers = biggest_climbers(&current, Some(&previous));
+
+        // Ties, B and C by four places and A and E by three, keep their
+        // current order.
+        assert_eq!(
+            climbs(&climbers),
+            [("B", 6, 2), ("C", 7, 3), ("A", 4, 1), ("E", 8, 5)]
+        );
+    }
+
+    #[test]
+    fn tracks_new_to_the_range_are_no_climbers() {
+        let current = tracks(TimeRange::ShortTerm, &["New", "Old"]);
+        let previous = tracks(TimeRange::MediumTerm, &["Other", "Another", "Old"]);
+
+        let climbers = biggest_climbers(&current, Some(&previous));
+
+        assert_eq!(climbs(&climbers), [("Old", 3, 2)]);
+        assert!(biggest_climbers(&current, None).is_empty());
+    }
+
+    #[test]
+    fn discoveries_are_artists_missing_from_the_longer_range() {
+        let current = artists(TimeRange::ShortTerm, &["Björk", "Portishead", "Tricky"]);
+        let previous = artists(TimeRange::MediumTerm, &["Massive Attack", "Portishead"]);
+
+        assert_eq!(
+            new_discoveries(&current, Some(&previous)),
+            ["Björk", "Tricky"]
+        );
+        assert!(new_discoveries(&current, None).is_empty());
+    }
+}
@@ -0,0 +1,42 @@
+use chrono::{DateTime, Datelike, Local};
+use rspotify::{model::TimeRange, AuthCodeSpotify, ClientError};
+
+use crate::albums::{get_top_albums, TopAlbums};
+use crate::client::{get_top_artists, get_top_tracks, TopArtists, TopTracks};
+use crate::eras::{get_release_eras, ReleaseEras};
+use crate::metrics::{get_listening_metrics, ListeningMetrics};
+
+/// Everything shown for a single time range.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RangeResults {
+    pub time_range: TimeRange,
+    pub tracks: TopTracks,
+    pub artists: TopArtists,
+    pub albums: TopAlbums,
+    pub eras: ReleaseEras,
+    pub metrics: ListeningMetrics,
+    pub fetched_at: DateTime<Local>,
+}
+
+pub async fn get_range_results(
+    client: &AuthCodeSpotify,
+    time_range: TimeRange,
+    limit: u8,
+) -> Result<RangeResults, ClientError> {
+    let tracks = get_top_tracks(client, time_range, limit).await?;
+    let artists = get_top_artists(client, time_range, limit).await?;
+
+    let fetched_at = Local::now();
+
+    let result = RangeResults {
+        time_range,
+        fetched_at,
+        albums: get_top_albums(&tracks),
+        eras: get_release_eras(&tracks, fetched_at.year()),
+        metrics: get_listening_metrics(&tracks, &artists),
+        tracks,
+        artists,
+    };
+
+    Ok(result)
+}
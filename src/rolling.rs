@@ -0,0 +1,383 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use chrono::NaiveDate;
+use futures::{pin_mut, TryStreamExt};
+use rspotify::clients::OAuthClient;
+use rspotify::model::{Id, PlaylistId, TimeRange};
+use rspotify::{AuthCodeSpotify, ClientError};
+use serde::{Deserialize, Serialize};
+
+use crate::client::{get_top_tracks, TopTracks};
+use crate::playlist::{
+    self, render_name, ExistingPlaylist, PlaylistError, PlaylistPlan, PlaylistTracks,
+    TemplateError, WriteMode,
+};
+use crate::state::{self, StateError};
+
+/// How many top tracks a rolling playlist gets unless the config says
+/// otherwise.
+pub const DEFAULT_COUNT: u8 = 30;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Rotation {
+    /// A new playlist every month, kept up to date until the month is over.
+    Monthly,
+    /// A single playlist, replaced on every sync.
+    Current,
+}
+
+impl Rotation {
+    fn default_name(&self) -> &'static str {
+        match self {
+            Rotation::Monthly => "Top Tracks – {month}",
+            Rotation::Current => "Current Top {count}",
+        }
+    }
+
+    fn slug(&self) -> &'static str {
+        match self {
+            Rotation::Monthly => "monthly",
+            Rotation::Current => "current",
+        }
+    }
+}
+
+/// A playlist kept up to date from the short term top tracks, read from a
+/// `[[rolling]]` table of the config.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RollingPlaylist {
+    pub rotation: Rotation,
+    /// Name template, see [`render_name`]. It also tells the playlists of
+    /// different entries apart, so changing it starts new playlists.
+    pub name: Option<String>,
+    /// How many top tracks to write, at most 50.
+    #[serde(default = "default_count")]
+    pub count: u8,
+    #[serde(default)]
+    pub public: bool,
+    /// How many monthly playlists to keep. Older ones are deleted, and all
+    /// of them are kept when it is `None`.
+    pub keep: Option<usize>,
+}
+
+fn default_count() -> u8 {
+    DEFAULT_COUNT
+}
+
+impl RollingPlaylist {
+    pub fn new(rotation: Rotation) -> RollingPlaylist {
+        RollingPlaylist {
+            rotation,
+            name: None,
+            count: DEFAULT_COUNT,
+            public: false,
+            keep: None,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        self.name
+            .as_deref()
+            .unwrap_or_else(|| self.rotation.default_name())
+    }
+
+    pub fn validate(&self) -> Result<(), TemplateError> {
+        let date = NaiveDate::from_ymd_opt(2000, 1, 1).unwrap_or_default();
+        render_name(self.name(), &TimeRange::ShortTerm, 0, date).map(|_| ())
+    }
+
+    fn count(&self) -> usize {
+        self.count.clamp(1, 50) as usize
+    }
+
+    /// Identifies the playlists made for this entry in the state.
+    fn slot(&self) -> String {
+        format!("{}:{}", self.rotation.slug(), self.name())
+    }
+
+    /// The month a monthly playlist written on `date` is for.
+    fn month(&self, date: NaiveDate) -> Option<String> {
+        match self.rotation {
+            Rotation::Monthly => Some(date.format("%Y-%m").to_string()),
+            Rotation::Current => None,
+        }
+    }
+}
+
+/// The playlists synced when the config has no `[[rolling]]` tables.
+pub fn default_playlists() -> Vec<RollingPlaylist> {
+    vec![
+        RollingPlaylist::new(Rotation::Monthly),
+        RollingPlaylist::new(Rotation::Current),
+    ]
+}
+
+/// A playlist made by a sync, which later syncs may change or delete.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OwnedPlaylist {
+    /// See [`RollingPlaylist::slot`].
+    pub slot: String,
+    /// The month of a monthly playlist, as `2026-10`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub month: Option<String>,
+    pub id: String,
+}
+
+/// The playlists made by syncs. Nothing else is ever changed, so the user's
+/// own playlists are safe even when their names match.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RollingState {
+    #[serde(default)]
+    pub playlists: Vec<OwnedPlaylist>,
+}
+
+/// `$XDG_DATA_HOME/spotifystats/rolling.toml` or the platform equivalent.
+pub fn default_state_path() -> Option<PathBuf> {
+    dirs::data_dir().map(|dir| dir.join("spotifystats").join("rolling.toml"))
+}
+
+impl RollingState {
+    /// Reads the state at `path`, which is empty before the first sync.
+    pub fn load(path: &Path) -> Result<RollingState, StateError> {
+        state::load(path)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), StateError> {
+        state::save(self, path)
+    }
+
+    fn owned(&self, slot: &str, month: Option<&str>) -> Option<&OwnedPlaylist> {
+        self.playlists
+            .iter()
+            .find(|owned| owned.slot == slot && owned.month.as_deref() == month)
+    }
+
+    /// Records `playlist`, replacing whatever was recorded for its slot and
+    /// month.
+    fn record(&mut self, playlist: OwnedPlaylist) {
+        self.forget(&playlist.slot, playlist.month.as_deref());
+        self.playlists.push(playlist);
+    }
+
+    fn forget(&mut self, slot: &str, month: Option<&str>) {
+        self.playlists
+            .retain(|owned| !(owned.slot == slot && owned.month.as_deref() == month));
+    }
+
+    /// The monthly playlists of `slot` that fall outside the newest `keep`
+    /// months, counting `current` as one of them even before it is made.
+    fn expired(&self, slot: &str, keep: usize, current: &str) -> Vec<OwnedPlaylist> {
+        let mut owned: Vec<&OwnedPlaylist> = self
+            .playlists
+            .iter()
+            .filter(|owned| owned.slot == slot && owned.month.is_some())
+            .collect();
+        owned.sort_by(|a, b| b.month.cmp(&a.month));
+
+        let made = owned
+            .iter()
+            .any(|owned| owned.month.as_deref() == Some(current));
+        // The playlist just written is always kept.
+        let keep = keep.max(1);
+        let keep = if made { keep } else { keep - 1 };
+
+        owned.into_iter().skip(keep).cloned().collect()
+    }
+}
+
+/// The name and track count of every playlist the user follows, by id.
+async fn followed_playlists(
+    client: &AuthCodeSpotify,
+) -> Result<HashMap<String, (String, u32)>, ClientError> {
+    let stream = client.current_user_playlists();
+    pin_mut!(stream);
+
+    let mut playlists = HashMap::new();
+    while let Some(playlist) = stream.try_next().await? {
+        playlists.insert(
+            playlist.id.id().to_string(),
+            (playlist.name, playlist.tracks.total),
+        );
+    }
+
+    Ok(playlists)
+}
+
+/// Brings each of `playlists` up to date with the short term top tracks as
+/// of `date`, recording any playlist it makes in `state` right away so that
+/// a failed sync can be safely run again. Returns what was done, or with
+/// `dry_run` what would be.
+pub async fn sync(
+    client: &AuthCodeSpotify,
+    playlists: &[RollingPlaylist],
+    state: &mut RollingState,
+    date: NaiveDate,
+    dry_run: bool,
+) -> Result<Vec<String>, PlaylistError> {
+    let limit = playlists.iter().map(|p| p.count()).max().unwrap_or(0);
+    let top_tracks = get_top_tracks(client, TimeRange::ShortTerm, limit as u8).await?;
+    let user_id = client.me().await?.id;
+    let followed = followed_playlists(client).await?;
+    let mut report = Vec::new();
+
+    for playlist in playlists {
+        let slot = playlist.slot();
+        let month = playlist.month(date);
+        let top_tracks = TopTracks {
+            time_range: TimeRange::ShortTerm,
+            tracks: top_tracks
+                .tracks
+                .iter()
+                .take(playlist.count())
+                .cloned()
+                .collect(),
+        };
+        let PlaylistTracks {
+            tracks, skipped, ..
+        } = PlaylistTracks::from_top_tracks(&top_tracks);
+        if tracks.is_empty() {
+            return Err(PlaylistError::NoTracks);
+        }
+        let name = render_name(playlist.name(), &TimeRange::ShortTerm, tracks.len(), date)?;
+
+        // A recorded playlist the user has since deleted is made again.
+        let (existing, old_name) = state
+            .owned(&slot, month.as_deref())
+            .and_then(|owned| {
+                let (old_name, tracks) = followed.get(&owned.id)?;
+                let id = PlaylistId::from_id(owned.id.clone()).ok()?;
+                Some((
+                    ExistingPlaylist {
+                        id,
+                        tracks: *tracks,
+                    },
+                    old_name,
+                ))
+            })
+            .unzip();
+
+        let plan = PlaylistPlan {
+            user_id: user_id.clone(),
+            name,
+            existing,
+            public: playlist.public,
+            mode: WriteMode::Replace,
+            time_range: TimeRange::ShortTerm,
+            tracks,
+            skipped,
+        };
+
+        if dry_run {
+            report.push(plan.summary());
+        } else {
+            let id = playlist::write(client, &plan).await?;
+            state.record(OwnedPlaylist {
+                slot: slot.clone(),
+                month: month.clone(),
+                id: id.id().to_string(),
+            });
+            if old_name.is_some_and(|old_name| *old_name != plan.name) {
+                client
+                    .playlist_change_detail(id.as_ref(), Some(&plan.name), None, None, None)
+                    .await?;
+            }
+            report.push(plan.done_message());
+        }
+
+        if let (Some(keep), Some(month)) = (playlist.keep, &month) {
+            for expired in state.expired(&slot, keep, month) {
+                let expired_month = expired.month.as_deref().unwrap_or_default();
+                if dry_run {
+                    report.push(format!("Delete the playlist for {}", expired_month));
+                    continue;
+                }
+                if followed.contains_key(&expired.id) {
+                    if let Ok(id) = PlaylistId::from_id(expired.id.as_str()) {
+                        client.playlist_unfollow(id).await?;
+                    }
+                }
+                state.forget(&slot, expired.month.as_deref());
+                report.push(format!("Deleted the playlist for {}", expired_month));
+            }
+        }
+    }
+
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn owned(slot: &str, month: Option<&str>, id: &str) -> OwnedPlaylist {
+        OwnedPlaylist {
+            slot: slot.to_string(),
+            month: month.map(str::to_string),
+            id: id.to_string(),
+        }
+    }
+
+    #[test]
+    fn monthly_playlists_get_a_slot_per_month() {
+        let monthly = RollingPlaylist::new(Rotation::Monthly);
+        let current = RollingPlaylist::new(Rotation::Current);
+        let date = NaiveDate::from_ymd_opt(2026, 10, 18).unwrap();
+
+        assert_eq!(monthly.slot(), "monthly:Top Tracks – {month}");
+        assert_eq!(monthly.month(date).as_deref(), Some("2026-10"));
+        assert_eq!(current.slot(), "current:Current Top {count}");
+        assert_eq!(current.month(date), None);
+    }
+
+    #[test]
+    fn recording_a_playlist_replaces_its_slot() {
+        let mut state = RollingState::default();
+        state.record(owned("current:Top", None, "first"));
+        state.record(owned("monthly:Top", Some("2026-09"), "september"));
+        state.record(owned("current:Top", None, "second"));
+
+        assert_eq!(state.playlists.len(), 2);
+        assert_eq!(state.owned("current:Top", None).unwrap().id, "second");
+        assert_eq!(state.owned("monthly:Top", Some("2026-10")), None);
+    }
+
+    #[test]
+    fn expires_months_beyond_keep() {
+        let mut state = RollingState::default();
+        for month in ["2026-07", "2026-08", "2026-09"] {
+            state.record(owned("monthly:Top", Some(month), month));
+        }
+        state.record(owned("monthly:Other", Some("2026-06"), "other"));
+        state.record(owned("current:Top", None, "current"));
+
+        // October isn't made yet but already counts as one of the two.
+        let expired: Vec<String> = state
+            .expired("monthly:Top", 2, "2026-10")
+            .into_iter()
+            .map(|owned| owned.id)
+            .collect();
+        assert_eq!(expired, ["2026-08", "2026-07"]);
+
+        state.record(owned("monthly:Top", Some("2026-10"), "2026-10"));
+        assert_eq!(state.expired("monthly:Top", 2, "2026-10").len(), 2);
+        assert!(state.expired("monthly:Top", 4, "2026-10").is_empty());
+    }
+
+    #[test]
+    fn state_survives_a_round_trip() {
+        let path =
+            std::env::temp_dir().join(format!("spotifystats-rolling-{}.toml", std::process::id()));
+        let mut state = RollingState::load(&path).unwrap();
+        assert_eq!(state, RollingState::default());
+
+        state.record(owned("monthly:Top", Some("2026-10"), "october"));
+        state.record(owned("current:Top", None, "current"));
+        state.save(&path).unwrap();
+
+        assert_eq!(RollingState::load(&path).unwrap(), state);
+        std::fs::remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,108 @@
+use unicode_normalization::{char::is_combining_mark, UnicodeNormalization};
+
+/// Lowercases `text` and strips accents, remembering for every resulting
+/// character the index of the character of `text` it came from.
+fn normalize(text: &str) -> Vec<(char, usize)> {
+    text.chars()
+        .enumerate()
+        .flat_map(|(i, c)| {
+            c.to_string()
+                .nfd()
+                .filter(|c| !is_combining_mark(*c))
+                .flat_map(char::to_lowercase)
+                .map(move |c| (c, i))
+                .collect::<Vec<(char, usize)>>()
+        })
+        .collect()
+}
+
+/// Matches `query` against `text` ignoring case and accents.
+///
+/// A contiguous occurrence is preferred; otherwise the characters of the
+/// query only have to appear in order. Returns the indices of the matched
+/// characters of `text`, or `None` when it doesn't match. An empty query
+/// matches everything without highlighting anything.
+pub fn fuzzy_match(text: &str, query: &str) -> Option<Vec<usize>> {
+    let needle: Vec<char> = normalize(query).into_iter().map(|(c, _)| c).collect();
+    if needle.is_empty() {
+        return Some(Vec::new());
+    }
+
+    let haystack = normalize(text);
+    let chars: Vec<char> = haystack.iter().map(|(c, _)| *c).collect();
+
+    let mut indices: Vec<usize> = match chars.windows(needle.len()).position(|w| w == needle) {
+        Some(start) => haystack[start..start + needle.len()]
+            .iter()
+            .map(|(_, i)| *i)
+            .collect(),
+        None => {
+            let mut needle = needle.iter().peekable();
+            let mut indices = Vec::new();
+
+            for (c, i) in &haystack {
+                if needle.peek() == Some(&c) {
+                    needle.next();
+                    indices.push(*i);
+                }
+            }
+
+            if needle.peek().is_some() {
+                return None;
+            }
+            indices
+        }
+    };
+
+    indices.dedup();
+    Some(indices)
+}
+
+/// Whether any of `fields` matches `query`.
+pub fn matches_any<'a, I>(fields: I, query: &str) -> bool
+where
+    I: IntoIterator<Item = &'a str>,
+{
+    fields
+        .into_iter()
+        .any(|field| fuzzy_match(field, query).is_some())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ignores_case_and_accents() {
+        assert_eq!(fuzzy_match("Björk", "bjork"), Some(vec![0, 1, 2, 3, 4]));
+        assert_eq!(fuzzy_match("bjork", "BJÖRK"), Some(vec![0, 1, 2, 3, 4]));
+    }
+
+    #[test]
+    fn falls_back_to_characters_in_order() {
+        assert_eq!(fuzzy_match("Paranoid Android", "pnd"), Some(vec![0, 4, 7]));
+        assert_eq!(
+            fuzzy_match("Paranoid Android", "and"),
+            Some(vec![9, 10, 11])
+        );
+        assert_eq!(fuzzy_match("Teardrop", "pot"), None);
+    }
+
+    #[test]
+    fn maps_decomposed_characters_back_to_the_text() {
+        // ệ decomposes into an e and two combining marks.
+        assert_eq!(fuzzy_match("Tiệm", "tiem"), Some(vec![0, 1, 2, 3]));
+        // A combining mark in the text matches nothing itself.
+        assert_eq!(
+            fuzzy_match("Beyonce\u{301} Knowles", "beyoncé k"),
+            Some(vec![0, 1, 2, 3, 4, 5, 6, 8, 9])
+        );
+    }
+
+    #[test]
+    fn an_empty_query_matches_without_highlights() {
+        assert_eq!(fuzzy_match("Teardrop", ""), Some(Vec::new()));
+        assert_eq!(fuzzy_match("", ""), Some(Vec::new()));
+        assert_eq!(fuzzy_match("", "a"), None);
+    }
+}
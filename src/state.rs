@@ -0,0 +1,47 @@
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+/// Failure to read or write one of the TOML files kept between runs, like
+/// the rolling playlists or the seen releases.
+#[derive(Debug)]
+pub enum StateError {
+    Read(PathBuf, std::io::Error),
+    Parse(PathBuf, toml::de::Error),
+    Write(PathBuf, std::io::Error),
+    Serialize(toml::ser::Error),
+}
+
+impl fmt::Display for StateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StateError::Read(path, e) => write!(f, "could not read {}: {}", path.display(), e),
+            StateError::Parse(path, e) => write!(f, "invalid state {}: {}", path.display(), e),
+            StateError::Write(path, e) => write!(f, "could not write {}: {}", path.display(), e),
+            StateError::Serialize(e) => write!(f, "could not save state: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for StateError {}
+
+/// Reads the state at `path`, which is the default one until it is first
+/// saved.
+pub fn load<T: DeserializeOwned + Default>(path: &Path) -> Result<T, StateError> {
+    match std::fs::read_to_string(path) {
+        Ok(text) => toml::from_str(&text).map_err(|e| StateError::Parse(path.to_path_buf(), e)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(T::default()),
+        Err(e) => Err(StateError::Read(path.to_path_buf(), e)),
+    }
+}
+
+/// Writes `state` to `path`, creating its directory when needed.
+pub fn save<T: Serialize>(state: &T, path: &Path) -> Result<(), StateError> {
+    let text = toml::to_string(state).map_err(StateError::Serialize)?;
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(|e| StateError::Write(dir.to_path_buf(), e))?;
+    }
+    std::fs::write(path, text).map_err(|e| StateError::Write(path.to_path_buf(), e))
+}
@@ -0,0 +1,250 @@
+use std::fmt;
+use std::str::FromStr;
+
+use ratatui::style::{Color, Modifier, Style, Stylize};
+use serde::Deserialize;
+
+/// The styles every part of the TUI is drawn with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Theme {
+    /// The background the theme is made for. The TUI keeps the terminal's
+    /// own background, so only exports like the stats card paint it.
+    pub background: Color,
+    /// Borders and titles of the focused pane and of popups.
+    pub border: Style,
+    /// Borders of the panes that are not focused.
+    pub border_inactive: Style,
+    /// Table headers and section titles.
+    pub header: Style,
+    /// Rank numbers, list markers and field names.
+    pub index: Style,
+    /// Values next to field names.
+    pub value: Style,
+    /// The selected row and other highlighted labels.
+    pub selected: Style,
+    /// Characters matching the search query.
+    pub matched: Style,
+    /// Hints, placeholders and other secondary text.
+    pub muted: Style,
+    pub chart: Style,
+    pub chart_value: Style,
+    /// The track playing right now, in the top lists and the progress bar.
+    pub playing: Style,
+    pub success: Style,
+    pub error: Style,
+}
+
+pub const BUILTIN_THEMES: [&str; 4] = ["dark", "light", "high-contrast", "monochrome"];
+
+impl Theme {
+    pub fn dark() -> Theme {
+        Theme {
+            background: Color::Black,
+            border: Style::new().green(),
+            border_inactive: Style::new().dark_gray(),
+            header: Style::new().green().add_modifier(Modifier::BOLD),
+            index: Style::new().green().add_modifier(Modifier::ITALIC),
+            value: Style::new().white(),
+            selected: Style::new().black().on_green(),
+            matched: Style::new().yellow().add_modifier(Modifier::BOLD),
+            muted: Style::new().dark_gray(),
+            chart: Style::new().green(),
+            chart_value: Style::new().black().on_green(),
+            playing: Style::new().light_green().add_modifier(Modifier::BOLD),
+            success: Style::new().green(),
+            error: Style::new().red().add_modifier(Modifier::BOLD),
+        }
+    }
+
+    /// For terminals with a light background, where white and yellow text
+    /// would be unreadable.
+    pub fn light() -> Theme {
+        Theme {
+            background: Color::White,
+            border: Style::new().blue(),
+            border_inactive: Style::new().gray(),
+            header: Style::new().blue().add_modifier(Modifier::BOLD),
+            index: Style::new().blue().add_modifier(Modifier::ITALIC),
+            value: Style::new().black(),
+            selected: Style::new().white().on_blue(),
+            matched: Style::new().magenta().add_modifier(Modifier::BOLD),
+            muted: Style::new().dark_gray(),
+            chart: Style::new().blue(),
+            chart_value: Style::new().white().on_blue(),
+            playing: Style::new().blue().add_modifier(Modifier::BOLD),
+            success: Style::new().blue(),
+            error: Style::new().red().add_modifier(Modifier::BOLD),
+        }
+    }
+
+    /// Bright, bold colors on black with no dim text.
+    pub fn high_contrast() -> Theme {
+        Theme {
+            background: Color::Black,
+            border: Style::new().light_yellow().add_modifier(Modifier::BOLD),
+            border_inactive: Style::new().white(),
+            header: Style::new()
+                .light_yellow()
+                .add_modifier(Modifier::BOLD | Modifier::UNDERLINED),
+            index: Style::new().light_cyan().add_modifier(Modifier::BOLD),
+            value: Style::new().white().add_modifier(Modifier::BOLD),
+            selected: Style::new()
+                .black()
+                .on_light_yellow()
+                .add_modifier(Modifier::BOLD),
+            matched: Style::new()
+                .black()
+                .on_light_cyan()
+                .add_modifier(Modifier::BOLD),
+            muted: Style::new().white(),
+            chart: Style::new().light_yellow(),
+            chart_value: Style::new()
+                .black()
+                .on_light_yellow()
+                .add_modifier(Modifier::BOLD),
+            playing: Style::new()
+                .light_green()
+                .add_modifier(Modifier::BOLD | Modifier::UNDERLINED),
+            success: Style::new().light_green().add_modifier(Modifier::BOLD),
+            error: Style::new().white().on_red().add_modifier(Modifier::BOLD),
+        }
+    }
+
+    /// No colors at all, only text attributes, for `NO_COLOR` and terminals
+    /// without color support.
+    pub fn monochrome() -> Theme {
+        Theme {
+            background: Color::Reset,
+            border: Style::new().add_modifier(Modifier::BOLD),
+            border_inactive: Style::new(),
+            header: Style::new().add_modifier(Modifier::BOLD | Modifier::UNDERLINED),
+            index: Style::new().add_modifier(Modifier::ITALIC),
+            value: Style::new(),
+            selected: Style::new().add_modifier(Modifier::REVERSED),
+            matched: Style::new().add_modifier(Modifier::BOLD | Modifier::UNDERLINED),
+            muted: Style::new().add_modifier(Modifier::DIM),
+            chart: Style::new(),
+            chart_value: Style::new().add_modifier(Modifier::REVERSED),
+            playing: Style::new().add_modifier(Modifier::BOLD | Modifier::ITALIC),
+            success: Style::new().add_modifier(Modifier::BOLD),
+            error: Style::new().add_modifier(Modifier::BOLD | Modifier::REVERSED),
+        }
+    }
+
+    pub fn builtin(name: &str) -> Option<Theme> {
+        match name {
+            "dark" => Some(Theme::dark()),
+            "light" => Some(Theme::light()),
+            "high-contrast" => Some(Theme::high_contrast()),
+            "monochrome" => Some(Theme::monochrome()),
+            _ => None,
+        }
+    }
+
+    /// Replaces the colors of the base theme with the ones set in `colors`.
+    ///
+    /// The accent is used for foregrounds and, where the base theme fills a
+    /// background with it, for that background too.
+    pub fn with_colors(self, colors: &ThemeColors) -> Result<Theme, ThemeError> {
+        let mut theme = self;
+
+        if let Some(accent) = parse_color(&colors.accent)? {
+            for style in [
+                &mut theme.border,
+                &mut theme.header,
+                &mut theme.index,
+                &mut theme.chart,
+                &mut theme.playing,
+                &mut theme.success,
+            ] {
+                *style = style.fg(accent);
+            }
+            for style in [&mut theme.selected, &mut theme.chart_value] {
+                *style = style.bg(accent);
+            }
+        }
+        if let Some(background) = parse_color(&colors.background)? {
+            theme.background = background;
+        }
+        if let Some(foreground) = parse_color(&colors.foreground)? {
+            theme.value = theme.value.fg(foreground);
+        }
+        if let Some(muted) = parse_color(&colors.muted)? {
+            theme.muted = theme.muted.fg(muted);
+            theme.border_inactive = theme.border_inactive.fg(muted);
+        }
+        if let Some(matched) = parse_color(&colors.matched)? {
+            theme.matched = theme.matched.fg(matched);
+        }
+        if let Some(error) = parse_color(&colors.error)? {
+            theme.error = theme.error.fg(error);
+        }
+
+        Ok(theme)
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Theme {
+        Theme::dark()
+    }
+}
+
+/// A named theme from the config, e.g.
+///
+/// ```toml
+/// [themes.ocean]
+/// base = "dark"
+/// background = "#001b2e"
+/// accent = "cyan"
+/// matched = "#ffaf00"
+/// ```
+///
+/// Colors take the names ratatui understands, `#rrggbb` or a 0-255 index.
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ThemeColors {
+    pub base: Option<String>,
+    pub background: Option<String>,
+    pub accent: Option<String>,
+    pub foreground: Option<String>,
+    pub muted: Option<String>,
+    pub matched: Option<String>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ThemeError {
+    UnknownTheme(String),
+    InvalidColor(String),
+}
+
+impl fmt::Display for ThemeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ThemeError::UnknownTheme(name) => write!(
+                f,
+                "unknown theme {:?}, expected one of the [themes] in the config or {}",
+                name,
+                BUILTIN_THEMES.join(", ")
+            ),
+            ThemeError::InvalidColor(color) => write!(f, "invalid color {:?}", color),
+        }
+    }
+}
+
+impl std::error::Error for ThemeError {}
+
+fn parse_color(color: &Option<String>) -> Result<Option<Color>, ThemeError> {
+    match color {
+        Some(color) => Color::from_str(color)
+            .map(Some)
+            .map_err(|_| ThemeError::InvalidColor(color.clone())),
+        None => Ok(None),
+    }
+}
+
+/// Whether the user asked for no colors, see <https://no-color.org>.
+pub fn no_color() -> bool {
+    std::env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty())
+}